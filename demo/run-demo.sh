@@ -16,14 +16,23 @@ printf '\n%s\n' ====================
 # Set params
 press_articles_dir="demo/press/docs/articles"
 press_images_dir="demo/press/docs/public/images"
+press_books_dir="demo/press/docs/books"
 
-find $press_articles_dir -mindepth 1 -not -name '.gitignore' -delete
-find $press_images_dir -mindepth 1 -not -name '.gitignore' -delete
+find $press_books_dir -mindepth 1 -not -name '.gitignore' -delete
 
-target/release/zenn2press \
+# List the Zenn content
+target/release/zenn2press list -s demo/zenn
+
+printf '\n%s\n' ====================
+
+# Convert
+target/release/zenn2press convert \
     -c demo/zenn2press-config.json \
     -d $press_articles_dir \
     -m $press_images_dir \
+    -b $press_books_dir \
     -s demo/zenn \
-    -i sample-article-1 
+    -i sample-article-1 \
+    --include-book sample-book \
+    --prune
 
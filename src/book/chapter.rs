@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::config::BookConfig;
+use crate::error::{IoResultExt, Result};
+
+/// `Chapter` is a chapter file of a Zenn book.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    /// The slug of the chapter, used as the page name.
+    pub slug: String,
+    /// The path of the chapter's Markdown file.
+    pub path: PathBuf,
+}
+
+/// Returns the chapters of a book in reading order.
+///
+/// If the book's `config.yaml` lists `chapters`, the chapters are returned in that
+/// order and each slug is resolved to `<slug>.md`. Otherwise the chapters are the
+/// files named `<number>.<slug>.md`, ordered by their numeric prefix.
+///
+/// # Arguments
+///
+/// * `book_dir` - The directory of the book.
+/// * `config` - The parsed `config.yaml` of the book.
+/// * `warnings` - A list where the chapters listed in `config.yaml` but not found are added.
+///
+/// # Returns
+///
+/// * `Result<Vec<Chapter>>` - The chapters of the book in reading order, or an `Error::Io` if the directory cannot be read.
+pub fn get_chapters(
+    book_dir: &Path,
+    config: &BookConfig,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<Chapter>> {
+    // Use the explicit chapter order from the config, if provided
+    if let Some(slugs) = &config.chapters {
+        let mut chapters = Vec::new();
+        for slug in slugs {
+            let path = book_dir.join(format!("{}.md", slug));
+            if !path.is_file() {
+                warnings.push(format!("Chapter {:?} not found", slug));
+                continue;
+            }
+            chapters.push(Chapter {
+                slug: slug.clone(),
+                path,
+            });
+        }
+        return Ok(chapters);
+    }
+
+    // Otherwise, collect `<number>.<slug>.md` files
+    let mut numbered: Vec<(u32, Chapter)> = fs::read_dir(book_dir)
+        .with_path(book_dir)?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let (number, slug) = parse_numbered_file_name(&path)?;
+            Some((number, Chapter { slug, path }))
+        })
+        .collect();
+
+    // Sort the chapters by their numeric prefix
+    numbered.sort_by_key(|(number, _)| *number);
+
+    Ok(numbered.into_iter().map(|(_, chapter)| chapter).collect())
+}
+
+/// Parses a file name of the form `<number>.<slug>.md`.
+fn parse_numbered_file_name(path: &Path) -> Option<(u32, String)> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+        return None;
+    }
+
+    let stem = path.file_stem()?.to_str()?;
+    let (number, slug) = stem.split_once('.')?;
+
+    Some((number.parse().ok()?, slug.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_get_chapters_with_config_order() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("intro.md"), "").unwrap();
+        fs::write(temp_dir.path().join("setup.md"), "").unwrap();
+
+        let config = BookConfig {
+            chapters: Some(vec![
+                "setup".to_string(),
+                "missing".to_string(),
+                "intro".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let mut warnings = Vec::new();
+        let chapters = get_chapters(temp_dir.path(), &config, &mut warnings).unwrap();
+
+        let slugs: Vec<&str> = chapters.iter().map(|c| c.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["setup", "intro"]);
+        assert_eq!(warnings, vec!["Chapter \"missing\" not found"]);
+    }
+
+    #[test]
+    fn test_get_chapters_with_numeric_prefix() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("10.appendix.md"), "").unwrap();
+        fs::write(temp_dir.path().join("2.setup.md"), "").unwrap();
+        fs::write(temp_dir.path().join("1.intro.md"), "").unwrap();
+        fs::write(temp_dir.path().join("config.yaml"), "").unwrap();
+        fs::write(temp_dir.path().join("notes.md"), "").unwrap();
+
+        let chapters =
+            get_chapters(temp_dir.path(), &BookConfig::default(), &mut Vec::new()).unwrap();
+
+        let slugs: Vec<&str> = chapters.iter().map(|c| c.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["intro", "setup", "appendix"]);
+        assert_eq!(chapters[0].path, temp_dir.path().join("1.intro.md"));
+    }
+}
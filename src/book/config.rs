@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, IoResultExt, Result};
+
+/// `BookConfig` is a structure representing the `config.yaml` of a Zenn book.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookConfig {
+    /// The title of the book.
+    pub title: String,
+
+    /// The summary of the book.
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The topics of the book.
+    #[serde(default)]
+    pub topics: Vec<String>,
+
+    /// Whether the book is published.
+    #[serde(default)]
+    pub published: Option<bool>,
+
+    /// The price of the book in yen (`0` for free books).
+    #[serde(default)]
+    pub price: Option<u32>,
+
+    /// The chapter slugs in reading order. When absent, the chapters are
+    /// ordered by the numeric prefix of their file names.
+    #[serde(default)]
+    pub chapters: Option<Vec<String>>,
+}
+
+/// Reads the `config.yaml` of a Zenn book.
+///
+/// # Arguments
+///
+/// * `config_file` - The path to the `config.yaml` file.
+///
+/// # Returns
+///
+/// * `Ok(BookConfig)` if the file is read and parsed successfully.
+/// * `Err(Error::Io)` if the file cannot be read.
+/// * `Err(Error::Config)` with the line of the error if the YAML cannot be parsed.
+pub fn read_book_config(config_file: &Path) -> Result<BookConfig> {
+    // Read the contents of the configuration file into a string
+    let config_content = fs::read_to_string(config_file).with_path(config_file)?;
+
+    // Parse the YAML string into a BookConfig struct
+    let config: BookConfig = serde_yaml::from_str(&config_content).map_err(|e| Error::Config {
+        path: config_file.to_path_buf(),
+        line: e.location().map(|l| l.line()),
+        message: e.to_string(),
+    })?;
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_read_book_config() {
+        let yaml_content = r#"
+title: "Sample Book"
+summary: "A book about VitePress"
+topics: ["vitepress", "zenn"]
+published: true
+price: 0
+chapters:
+  - intro
+  - setup
+"#;
+
+        let temp_dir = tempdir().unwrap();
+        let config_file = temp_dir.path().join("config.yaml");
+        fs::write(&config_file, yaml_content).unwrap();
+
+        let config = read_book_config(&config_file).unwrap();
+
+        assert_eq!(config.title, "Sample Book");
+        assert_eq!(config.summary.as_deref(), Some("A book about VitePress"));
+        assert_eq!(config.topics, vec!["vitepress", "zenn"]);
+        assert_eq!(config.price, Some(0));
+        assert_eq!(
+            config.chapters,
+            Some(vec!["intro".to_string(), "setup".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_read_book_config_without_chapters() {
+        let temp_dir = tempdir().unwrap();
+        let config_file = temp_dir.path().join("config.yaml");
+        fs::write(&config_file, "title: Book\n").unwrap();
+
+        let config = read_book_config(&config_file).unwrap();
+
+        assert_eq!(config.title, "Book");
+        assert!(config.topics.is_empty());
+        assert_eq!(config.chapters, None);
+    }
+}
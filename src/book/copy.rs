@@ -0,0 +1,283 @@
+use std::fs;
+use std::path::Path;
+
+use serde_yaml::Value;
+
+use super::{
+    chapter::get_chapters,
+    config::{read_book_config, BookConfig},
+};
+use crate::error::{Error, IoResultExt, Result};
+use crate::markdown::{
+    config::ConfigFile,
+    copy::{convert_markdown_file, merge_frontmatter},
+    frontmatter::{parse_frontmatter, Frontmatter},
+    transform::link::ImageLinks,
+};
+use crate::output::{write_output, OutputMode, Written};
+use crate::report::Action;
+use crate::sidebar::{write_sidebar_json, SidebarItem};
+
+/// The file names Zenn accepts for a book cover.
+const COVER_FILES: [&str; 4] = ["cover.png", "cover.jpg", "cover.jpeg", "cover.webp"];
+
+/// Converts a Zenn book into a VitePress directory.
+///
+/// The book is written to `<dest_dir>/<slug>/`, where each chapter becomes a page
+/// named after its slug, `index.md` lists the chapters in reading order and
+/// `sidebar.json` holds a sidebar fragment for the book. The cover image, if any,
+/// is copied next to the index page. Files that are up to date are not written again,
+/// and nothing is written in dry-run mode.
+///
+/// # Arguments
+///
+/// * `book_dir` - The directory of the Zenn book (`books/<slug>`).
+/// * `dest_dir` - The VitePress directory where books are placed.
+/// * `books_url` - The URL path of `dest_dir` on the site (e.g. `/books`).
+/// * `frontmatter_config` - Optional configuration data to merge with the frontmatter of each page.
+/// * `image_links` - Optional settings for rewriting `/images/...` URLs in the chapters.
+/// * `mode` - How the files of the book are written.
+/// * `warnings` - A list where the warnings raised by the conversion of the chapters are added.
+///
+/// # Returns
+///
+/// * `Result<(SidebarItem, Written)>` - The sidebar fragment of the book and the change to its
+///   directory, with the diffs of its pages if requested, or an error naming the offending file.
+pub fn copy_book(
+    book_dir: &Path,
+    dest_dir: &Path,
+    books_url: &str,
+    frontmatter_config: Option<&ConfigFile>,
+    image_links: Option<&ImageLinks>,
+    mode: OutputMode,
+    warnings: &mut Vec<String>,
+) -> Result<(SidebarItem, Written)> {
+    // Read the book's config.yaml and resolve its chapters
+    let slug = book_dir.file_name().unwrap().to_string_lossy().to_string();
+    let config = read_book_config(&book_dir.join("config.yaml"))?;
+    let chapters = get_chapters(book_dir, &config, warnings)?;
+
+    // Create the directory of the book
+    let book_dest_dir = dest_dir.join(&slug);
+    let created = !book_dest_dir.is_dir();
+    if !mode.dry_run {
+        fs::create_dir_all(&book_dest_dir).with_path(&book_dest_dir)?;
+    }
+    let mut written = Vec::new();
+    let book_url = format!("{}/{}", books_url.trim_end_matches('/'), slug);
+
+    // Convert each chapter and collect its sidebar entry
+    let mut items = Vec::new();
+    for chapter in &chapters {
+        let mut chapter_warnings = Vec::new();
+        let content = convert_markdown_file(
+            &chapter.path,
+            frontmatter_config,
+            image_links,
+            &mut chapter_warnings,
+        )?;
+        let file_name = chapter.path.file_name().unwrap_or_default();
+        warnings.extend(
+            chapter_warnings
+                .into_iter()
+                .map(|warning| format!("{} in {:?}", warning, file_name)),
+        );
+        let chapter_dest = book_dest_dir.join(format!("{}.md", chapter.slug));
+        written.push(write_output(&chapter_dest, &content, mode)?);
+
+        let title = chapter_title(&content)
+            .map_err(|e| e.with_path(&chapter_dest))?
+            .unwrap_or_else(|| chapter.slug.clone());
+        items.push(SidebarItem::page(
+            &title,
+            &format!("{}/{}", book_url, chapter.slug),
+        ));
+    }
+
+    // Copy the cover image, if any
+    let cover = COVER_FILES
+        .iter()
+        .find(|name| book_dir.join(name).is_file());
+    if let Some(cover) = cover {
+        let (src, dest) = (book_dir.join(cover), book_dest_dir.join(cover));
+        let changed = fs::read(&dest).ok() != Some(fs::read(&src).with_path(&src)?);
+        if changed && !mode.dry_run {
+            fs::copy(&src, &dest).with_path(&src)?;
+        }
+        let action = if changed {
+            Action::Update
+        } else {
+            Action::Unchanged
+        };
+        written.push(Written { action, diff: None });
+    }
+
+    // Write the index page and the sidebar fragment of the book
+    let index_path = book_dest_dir.join("index.md");
+    let index = render_index(&config, &items, cover.copied(), frontmatter_config)
+        .map_err(|e| e.with_path(&index_path))?;
+    written.push(write_output(&index_path, &index, mode)?);
+
+    let mut sidebar = SidebarItem::group(&config.title, items);
+    sidebar.link = Some(format!("{}/", book_url));
+    if !mode.dry_run {
+        write_sidebar_json(&book_dest_dir.join("sidebar.json"), &[sidebar.clone()])?;
+    }
+
+    Ok((sidebar, book_written(created, written)))
+}
+
+/// Combines the changes to the files of a book into the change to its directory.
+fn book_written(created: bool, written: Vec<Written>) -> Written {
+    let action = if created {
+        Action::Create
+    } else if written.iter().all(|w| w.action == Action::Unchanged) {
+        Action::Unchanged
+    } else {
+        Action::Update
+    };
+    let diffs: Vec<String> = written.into_iter().filter_map(|w| w.diff).collect();
+
+    Written {
+        action,
+        diff: (!diffs.is_empty()).then(|| diffs.concat()),
+    }
+}
+
+/// Reads the title from the frontmatter of a converted chapter.
+fn chapter_title(content: &str) -> Result<Option<String>> {
+    let (_, data) = parse_frontmatter(content)?;
+    Ok(data
+        .get("title")
+        .and_then(Value::as_str)
+        .map(str::to_string))
+}
+
+/// Renders the index page of a book from its `config.yaml`.
+fn render_index(
+    config: &BookConfig,
+    chapters: &[SidebarItem],
+    cover: Option<&str>,
+    frontmatter_config: Option<&ConfigFile>,
+) -> Result<String> {
+    // Build the frontmatter from the book's config
+    let mut data = Frontmatter::new();
+    data.insert("title".to_string(), Value::from(config.title.clone()));
+    if let Some(summary) = &config.summary {
+        data.insert("description".to_string(), Value::from(summary.clone()));
+    }
+    if !config.topics.is_empty() {
+        data.insert("topics".to_string(), Value::from(config.topics.clone()));
+    }
+    if let Some(published) = config.published {
+        data.insert("published".to_string(), Value::from(published));
+    }
+    if let Some(price) = config.price {
+        data.insert("price".to_string(), Value::from(price));
+    }
+    if let Some(frontmatter_config) = frontmatter_config {
+        merge_frontmatter(&mut data, &frontmatter_config.frontmatter);
+    }
+
+    // Build the body with the cover, summary and table of contents
+    let mut body = format!("# {}\n", config.title);
+    if let Some(cover) = cover {
+        body.push_str(&format!("\n![{}](./{})\n", config.title, cover));
+    }
+    if let Some(summary) = &config.summary {
+        body.push_str(&format!("\n{}\n", summary));
+    }
+    if !chapters.is_empty() {
+        body.push_str("\n## Chapters\n\n");
+        for (index, chapter) in chapters.iter().enumerate() {
+            let link = chapter.link.as_deref().unwrap_or_default();
+            body.push_str(&format!("{}. [{}]({})\n", index + 1, chapter.text, link));
+        }
+    }
+
+    Ok(format!(
+        "---\n{}---\n{}",
+        serde_yaml::to_string(&data).map_err(|e| Error::transform("", e))?,
+        body
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_copy_book() {
+        let src_dir = tempdir().unwrap();
+        let book_dir = src_dir.path().join("sample-book");
+        fs::create_dir_all(&book_dir).unwrap();
+        fs::write(
+            book_dir.join("config.yaml"),
+            "title: Sample Book\nsummary: About the book\nchapters:\n  - intro\n  - setup\n",
+        )
+        .unwrap();
+        fs::write(book_dir.join("intro.md"), "---\ntitle: Intro\n---\nHello").unwrap();
+        fs::write(
+            book_dir.join("setup.md"),
+            "---\ntitle: Setup\n---\n:::message\nNote\n:::",
+        )
+        .unwrap();
+        fs::write(book_dir.join("cover.png"), "").unwrap();
+
+        let dest_dir = tempdir().unwrap();
+        let mut warnings = Vec::new();
+        let (sidebar, written) = copy_book(
+            &book_dir,
+            dest_dir.path(),
+            "/books",
+            None,
+            None,
+            OutputMode::default(),
+            &mut warnings,
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(written.action, Action::Create);
+
+        // Each chapter becomes a converted page
+        let book_dest_dir = dest_dir.path().join("sample-book");
+        let setup = fs::read_to_string(book_dest_dir.join("setup.md")).unwrap();
+        assert!(setup.contains("# Setup\n::: info\nNote\n:::"));
+        assert!(book_dest_dir.join("intro.md").exists());
+        assert!(book_dest_dir.join("cover.png").exists());
+
+        // The index page lists the chapters in order
+        let index = fs::read_to_string(book_dest_dir.join("index.md")).unwrap();
+        assert!(index.contains("description: About the book"));
+        assert!(index.contains("![Sample Book](./cover.png)"));
+        assert!(index.contains(
+            "1. [Intro](/books/sample-book/intro)\n2. [Setup](/books/sample-book/setup)\n"
+        ));
+
+        // The sidebar fragment links to every chapter
+        assert_eq!(sidebar.text, "Sample Book");
+        assert_eq!(sidebar.link.as_deref(), Some("/books/sample-book/"));
+        assert_eq!(
+            sidebar.items,
+            vec![
+                SidebarItem::page("Intro", "/books/sample-book/intro"),
+                SidebarItem::page("Setup", "/books/sample-book/setup"),
+            ]
+        );
+        assert!(book_dest_dir.join("sidebar.json").exists());
+
+        // A second run finds the book up to date
+        let (_, written) = copy_book(
+            &book_dir,
+            dest_dir.path(),
+            "/books",
+            None,
+            None,
+            OutputMode::default(),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(written.action, Action::Unchanged);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use copy::copy_book;
+
+use crate::converter::Converter;
+use crate::error::{IoResultExt, Result};
+use crate::markdown::{
+    config::PathsConfig,
+    transform::link::{docs_url_path, ImageLinks},
+};
+use crate::progress_bar::get_pb;
+use crate::report::{Action, ConversionReport, FileKind, FileReport, FileStatus};
+
+pub mod chapter;
+pub mod config;
+pub mod copy;
+
+/// This function converts every Zenn book in the source directory into a VitePress
+/// directory in the destination directory, with options for filtering and configuring frontmatter.
+///
+/// It is a shim over [`Converter::convert_books`], kept for compatibility. The keep-going
+/// mode and other options are only available on the builder. The conversion stops with an
+/// error at the first book that fails.
+///
+/// # Arguments
+///
+/// * `src_dir` - The Zenn books directory path.
+/// * `dest_dir` - The destination directory path.
+/// * `config_file` - An optional path to a configuration file for frontmatter.
+/// * `include` - The optional list of book slugs to include in the processing.
+/// * `exclude` - The optional list of book slugs to exclude from the processing.
+/// * `images_dirs` - The optional source and destination images directories, used to
+///   rewrite `/images/...` URLs in the chapters.
+///
+/// # Examples
+///
+/// ```
+/// # #![allow(deprecated)]
+/// use zenn2press::copy_books;
+///
+/// fn main() -> zenn2press::Result<()> {
+///     let src_dir = "demo/zenn/books";
+///     let dest_dir = "demo/press/docs/books";
+///     let config_file = Some("demo/zenn2press-config.json");
+///
+///     copy_books(src_dir, dest_dir, config_file, None, None, None)?;
+///     Ok(())
+/// }
+/// ```
+#[deprecated(note = "use `Converter::builder()` and `Converter::convert_books` instead")]
+pub fn copy_books(
+    src_dir: &str,
+    dest_dir: &str,
+    config_file: Option<&str>,
+    include: Option<Vec<&str>>,
+    exclude: Option<Vec<&str>>,
+    images_dirs: Option<(&str, &str)>,
+) -> Result<()> {
+    let mut builder = Converter::builder()
+        .src_books_dir(src_dir)
+        .dest_books_dir(dest_dir)
+        .include_books(include.unwrap_or_default())
+        .exclude_books(exclude.unwrap_or_default());
+    if let Some(config_file) = config_file {
+        builder = builder.config_file(config_file);
+    }
+    if let Some((src_images_dir, dest_images_dir)) = images_dirs {
+        builder = builder
+            .src_images_dir(src_images_dir)
+            .dest_images_dir(dest_images_dir);
+    }
+
+    builder.build()?.convert_books()?.into_result()?;
+    Ok(())
+}
+
+/// Converts the books of a converter from the source directory to the destination directory.
+///
+/// A missing source directory is treated as empty.
+///
+/// # Arguments
+///
+/// * `converter` - The converter holding the configuration, filters and images directories.
+/// * `src_dir` - The Zenn books directory.
+/// * `dest_dir` - The VitePress directory of the books.
+///
+/// # Returns
+///
+/// * `Result<ConversionReport>` - The outcome of every book.
+pub(crate) fn convert_books(
+    converter: &Converter,
+    src_dir: &str,
+    dest_dir: &str,
+) -> Result<ConversionReport> {
+    let started = Instant::now();
+    let mode = converter.output_mode();
+    let mut conversion = ConversionReport {
+        dry_run: mode.dry_run,
+        ..Default::default()
+    };
+
+    // A Zenn repository without books has no books directory
+    if !Path::new(src_dir).is_dir() {
+        return Ok(conversion.timed(started));
+    }
+
+    // Collect the book directories that contain a config.yaml
+    let mut book_dirs: Vec<PathBuf> = fs::read_dir(src_dir)
+        .with_path(src_dir)?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.join("config.yaml").is_file())
+        .collect();
+    book_dirs.sort();
+
+    // Filter the books based on the book include and exclude lists
+    book_dirs.retain(|dir| converter.is_book_selected(&dir.file_name().unwrap().to_string_lossy()));
+    let frontmatter_config = converter.config();
+
+    // Resolve how `/images/...` URLs are rewritten, if the images directories are provided
+    let default_paths = PathsConfig::default();
+    let paths = frontmatter_config
+        .map(|config| &config.paths)
+        .unwrap_or(&default_paths);
+    let image_links = converter
+        .images_dirs()
+        .map(|(src_images_dir, dest_images_dir)| {
+            ImageLinks::new(src_images_dir, dest_images_dir, paths)
+        });
+
+    // Derive the URL of the books directory from its path below VitePress's `docs`
+    let dest_path = Path::new(dest_dir);
+    let books_url = docs_url_path(dest_path);
+
+    let file_length = book_dirs.len().try_into().unwrap();
+    let pb = get_pb(file_length);
+
+    for book_dir in book_dirs {
+        let slug = book_dir.file_name().unwrap().to_string_lossy().to_string();
+        pb.set_message(slug.clone());
+
+        // Convert each book into a directory in the dest directory.
+        // In keep-going mode, a failure is recorded and the next book is converted.
+        let book_started = Instant::now();
+        let mut warnings = Vec::new();
+        let entry = match copy_book(
+            &book_dir,
+            dest_path,
+            &books_url,
+            frontmatter_config,
+            image_links.as_ref(),
+            mode,
+            &mut warnings,
+        ) {
+            Ok((_, written)) => {
+                let status = match written.action {
+                    Action::Unchanged => FileStatus::Unchanged,
+                    _ => FileStatus::Converted,
+                };
+                let mut entry = FileReport::new(FileKind::Book, status, &book_dir);
+                entry.destination = Some(dest_path.join(slug));
+                entry.action = Some(written.action);
+                entry.diff = written.diff;
+                entry
+            }
+            Err(e) if converter.keep_going => FileReport::failed(FileKind::Book, &book_dir, e),
+            Err(e) => return Err(e),
+        };
+        conversion.push(FileReport { warnings, ..entry }.timed(book_started));
+        pb.inc(1)
+    }
+    pb.finish_with_message("Completed.");
+
+    Ok(conversion.timed(started))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Writes a book with a single chapter into the books directory.
+    fn write_book(books_dir: &Path, slug: &str) {
+        let book_dir = books_dir.join(slug);
+        fs::create_dir_all(&book_dir).unwrap();
+        fs::write(
+            book_dir.join("config.yaml"),
+            "title: Book\nchapters: [intro]\n",
+        )
+        .unwrap();
+        fs::write(book_dir.join("intro.md"), "---\ntitle: Intro\n---\nBody\n").unwrap();
+    }
+
+    #[test]
+    fn test_convert_books_with_book_filters() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        write_book(src_dir.path(), "a-book");
+        write_book(src_dir.path(), "b-book");
+        let builder = Converter::builder()
+            .src_books_dir(src_dir.path().to_string_lossy())
+            .dest_books_dir(dest_dir.path().to_string_lossy())
+            .include(["sample-article"]);
+
+        // The article include list does not apply to the books
+        let report = builder.clone().build().unwrap().convert_books().unwrap();
+        assert_eq!(report.summary().converted, 2);
+
+        let report = builder
+            .exclude_books(["a-book"])
+            .build()
+            .unwrap()
+            .convert_books()
+            .unwrap();
+        let converted: Vec<_> = report.files.iter().map(|file| &file.source).collect();
+        assert_eq!(converted, vec![&src_dir.path().join("b-book")]);
+    }
+
+    #[test]
+    fn test_convert_books_without_books_directory() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+
+        let report = Converter::builder()
+            .src_books_dir(src_dir.path().join("books").to_string_lossy())
+            .dest_books_dir(dest_dir.path().to_string_lossy())
+            .build()
+            .unwrap()
+            .convert_books()
+            .unwrap();
+        assert!(report.files.is_empty());
+    }
+}
@@ -1,3 +1 @@
 pub const DESCRIPTION: &str = "Convert your Zenn content to VitePress format";
-pub const ARTICLES_DIR: &str = "/articles";
-pub const IMAGES_DIR: &str = "/images";
@@ -0,0 +1,642 @@
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::inventory::{self, Inventory};
+use crate::markdown::{
+    config::{read_config_file, ConfigFile},
+    transform::TransformOptions,
+};
+use crate::output::OutputMode;
+use crate::report::ConversionReport;
+use crate::watch::Changes;
+use crate::{book, image, markdown, watch};
+
+/// The directory of the articles below the Zenn content root.
+pub const ARTICLES_DIR: &str = "articles";
+
+/// The directory of the images below the Zenn content root.
+pub const IMAGES_DIR: &str = "images";
+
+/// The directory of the books below the Zenn content root.
+pub const BOOKS_DIR: &str = "books";
+
+/// `Converter` converts Zenn content into VitePress content.
+///
+/// It is created with [`Converter::builder`], which resolves the directories, the
+/// configuration file and the filters once. Each kind of content is then converted
+/// with [`convert_articles`](Converter::convert_articles),
+/// [`convert_books`](Converter::convert_books) and
+/// [`convert_images`](Converter::convert_images), or all at once with [`run`](Converter::run).
+///
+/// # Examples
+///
+/// ```
+/// use zenn2press::Converter;
+///
+/// #[tokio::main]
+/// async fn main() -> zenn2press::Result<()> {
+///     let converter = Converter::builder()
+///         .src_dir("demo/zenn")
+///         .dest_dir("demo/press/docs/articles")
+///         .dest_images_dir("demo/press/docs/public/images")
+///         .config_file("demo/zenn2press-config.json")
+///         .include(["sample-article-1"])
+///         .build()?;
+///
+///     let report = converter.run().await?.into_result()?;
+///     println!("{}", report);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Converter {
+    /// The Zenn articles directory.
+    pub(crate) src_articles_dir: Option<String>,
+    /// The Zenn images directory.
+    pub(crate) src_images_dir: Option<String>,
+    /// The Zenn books directory.
+    pub(crate) src_books_dir: Option<String>,
+    /// The VitePress directory of the articles.
+    pub(crate) dest_dir: Option<String>,
+    /// The VitePress directory of the images.
+    pub(crate) dest_images_dir: Option<String>,
+    /// The VitePress directory of the books.
+    pub(crate) dest_books_dir: Option<String>,
+    /// The configuration, if any.
+    pub(crate) config: Option<ConfigFile>,
+    /// The path of the configuration file, used in error messages.
+    pub(crate) config_file: Option<String>,
+    /// The names of the files to include.
+    pub(crate) include: Vec<String>,
+    /// The names of the files to exclude.
+    pub(crate) exclude: Vec<String>,
+    /// The slugs of the books to include.
+    pub(crate) include_books: Vec<String>,
+    /// The slugs of the books to exclude.
+    pub(crate) exclude_books: Vec<String>,
+    /// Whether to keep converting the other files when one fails.
+    pub(crate) keep_going: bool,
+    /// Whether to delete the outputs whose source was removed.
+    pub(crate) prune: bool,
+    /// How the outputs are written.
+    pub(crate) output: OutputMode,
+}
+
+impl Converter {
+    /// Creates a builder for a `Converter`.
+    pub fn builder() -> ConverterBuilder {
+        ConverterBuilder::default()
+    }
+
+    /// Converts the articles into the destination directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The outcome of every article, or an error if the
+    ///   articles directories are not set or the conversion stops.
+    pub fn convert_articles(&self) -> Result<ConversionReport> {
+        let src_dir = self.required(&self.src_articles_dir, "src_dir")?;
+        let dest_dir = self.required(&self.dest_dir, "dest_dir")?;
+        markdown::convert_articles(self, src_dir, dest_dir)
+    }
+
+    /// Converts the books into the books destination directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The outcome of every book, or an error if the
+    ///   books directories are not set or the conversion stops.
+    pub fn convert_books(&self) -> Result<ConversionReport> {
+        let src_dir = self.required(&self.src_books_dir, "src_dir")?;
+        let dest_dir = self.required(&self.dest_books_dir, "dest_books_dir")?;
+        book::convert_books(self, src_dir, dest_dir)
+    }
+
+    /// Copies the images into the images destination directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The outcome of every image, or an error if the
+    ///   images directories are not set or the copy stops.
+    pub async fn convert_images(&self) -> Result<ConversionReport> {
+        let src_dir = self.required(&self.src_images_dir, "src_dir")?;
+        let dest_dir = self.required(&self.dest_images_dir, "dest_images_dir")?;
+        image::convert_images(self, src_dir, dest_dir).await
+    }
+
+    /// Converts the articles, then the books and the images if their destination directories are set.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The outcome of every file, or the error of the first step that stops.
+    pub async fn run(&self) -> Result<ConversionReport> {
+        let mut report = self.convert_articles()?;
+        if self.dest_books_dir.is_some() {
+            report.extend(self.convert_books()?);
+        }
+        if self.dest_images_dir.is_some() {
+            report.extend(self.convert_images().await?);
+        }
+        Ok(report)
+    }
+
+    /// Lists the articles, books and images the converter reads, with their metadata.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Inventory>` - The content found, or an error if the articles directory is not set or cannot be read.
+    pub async fn list(&self) -> Result<Inventory> {
+        let src_dir = self.required(&self.src_articles_dir, "src_dir")?;
+        inventory::list(self, src_dir).await
+    }
+
+    /// Converts the files affected by changes in the Zenn repository, e.g. reported by a [`Watch`](crate::watch::Watch).
+    ///
+    /// # Arguments
+    ///
+    /// * `changes` - The changed files.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The outcome of every converted file, or the error of the first step that stops.
+    pub async fn convert_changes(&self, changes: &Changes) -> Result<ConversionReport> {
+        watch::convert_changes(self, changes).await
+    }
+
+    /// Returns the configuration, if any.
+    pub fn config(&self) -> Option<&ConfigFile> {
+        self.config.as_ref()
+    }
+
+    /// Returns the include list in the form expected by the file filters.
+    pub(crate) fn include(&self) -> Option<Vec<&str>> {
+        Some(self.include.iter().map(String::as_str).collect())
+    }
+
+    /// Returns the exclude list in the form expected by the file filters.
+    pub(crate) fn exclude(&self) -> Option<Vec<&str>> {
+        Some(self.exclude.iter().map(String::as_str).collect())
+    }
+
+    /// Returns whether a book is selected by the book include and exclude lists.
+    pub(crate) fn is_book_selected(&self, slug: &str) -> bool {
+        let slug = slug.to_string();
+        let included = self.include_books.is_empty() || self.include_books.contains(&slug);
+        included && !self.exclude_books.contains(&slug)
+    }
+
+    /// Returns how the outputs are written.
+    pub fn output_mode(&self) -> OutputMode {
+        self.output
+    }
+
+    /// Returns the source and destination images directories, if both are set.
+    pub(crate) fn images_dirs(&self) -> Option<(&str, &str)> {
+        Some((
+            self.src_images_dir.as_deref()?,
+            self.dest_images_dir.as_deref()?,
+        ))
+    }
+
+    /// Returns a directory, or an `Error::Config` naming the missing setting.
+    fn required<'a>(&self, dir: &'a Option<String>, key: &str) -> Result<&'a str> {
+        dir.as_deref().ok_or_else(|| {
+            Error::config(
+                self.config_file.as_deref().unwrap_or_default(),
+                format!(
+                    "`{}` is not set (set it on the builder or as `paths.{}`)",
+                    key, key
+                ),
+            )
+        })
+    }
+}
+
+/// `ConverterBuilder` builds a [`Converter`].
+///
+/// Directories that are not set fall back to the `paths` section of the configuration
+/// file, and empty include and exclude lists fall back to its `filters` section. The
+/// include and exclude lists choose the articles and images, and the book lists the books. The
+/// articles, images and books directories are derived from the Zenn content root
+/// unless they are set individually.
+#[derive(Debug, Clone, Default)]
+pub struct ConverterBuilder {
+    src_dir: Option<String>,
+    converter: Converter,
+    transforms: Option<TransformOptions>,
+}
+
+impl ConverterBuilder {
+    /// Sets the root directory of the Zenn content, containing `articles`, `images` and `books`.
+    pub fn src_dir(mut self, dir: impl Into<String>) -> Self {
+        self.src_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the Zenn articles directory, instead of `<src_dir>/articles`.
+    pub fn src_articles_dir(mut self, dir: impl Into<String>) -> Self {
+        self.converter.src_articles_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the Zenn images directory, instead of `<src_dir>/images`.
+    pub fn src_images_dir(mut self, dir: impl Into<String>) -> Self {
+        self.converter.src_images_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the Zenn books directory, instead of `<src_dir>/books`.
+    pub fn src_books_dir(mut self, dir: impl Into<String>) -> Self {
+        self.converter.src_books_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the VitePress directory of the articles (e.g. `docs/entries`).
+    pub fn dest_dir(mut self, dir: impl Into<String>) -> Self {
+        self.converter.dest_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the VitePress directory of the images (e.g. `docs/public/images`).
+    pub fn dest_images_dir(mut self, dir: impl Into<String>) -> Self {
+        self.converter.dest_images_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the VitePress directory of the books (e.g. `docs/books`).
+    pub fn dest_books_dir(mut self, dir: impl Into<String>) -> Self {
+        self.converter.dest_books_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the configuration file, read when the converter is built.
+    pub fn config_file(mut self, file: impl Into<String>) -> Self {
+        self.converter.config_file = Some(file.into());
+        self
+    }
+
+    /// Sets the configuration, instead of reading it from a file.
+    pub fn config(mut self, config: ConfigFile) -> Self {
+        self.converter.config = Some(config);
+        self
+    }
+
+    /// Sets the names of the files to include.
+    pub fn include<I: IntoIterator<Item = S>, S: Into<String>>(mut self, names: I) -> Self {
+        self.converter.include = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the names of the files to exclude.
+    pub fn exclude<I: IntoIterator<Item = S>, S: Into<String>>(mut self, names: I) -> Self {
+        self.converter.exclude = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the slugs of the books to include.
+    pub fn include_books<I: IntoIterator<Item = S>, S: Into<String>>(mut self, slugs: I) -> Self {
+        self.converter.include_books = slugs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the slugs of the books to exclude.
+    pub fn exclude_books<I: IntoIterator<Item = S>, S: Into<String>>(mut self, slugs: I) -> Self {
+        self.converter.exclude_books = slugs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the transforms applied to the articles and chapters, instead of the `transforms` section of the configuration.
+    pub fn transforms(mut self, transforms: TransformOptions) -> Self {
+        self.transforms = Some(transforms);
+        self
+    }
+
+    /// Sets whether to keep converting the other files when one fails.
+    pub fn keep_going(mut self, keep_going: bool) -> Self {
+        self.converter.keep_going = keep_going;
+        self
+    }
+
+    /// Sets whether to delete the articles and images written by a previous run whose source was removed.
+    ///
+    /// Only the files listed in the manifest of each destination directory are deleted.
+    pub fn prune(mut self, prune: bool) -> Self {
+        self.converter.prune = prune;
+        self
+    }
+
+    /// Sets whether to convert the files without writing or deleting anything.
+    ///
+    /// The report then lists the planned changes, with the [`Action`](crate::report::Action) of each file.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.converter.output.dry_run = dry_run;
+        self
+    }
+
+    /// Sets whether to report a unified diff of each Markdown output against the file on disk.
+    pub fn diff(mut self, diff: bool) -> Self {
+        self.converter.output.diff = diff;
+        self
+    }
+
+    /// Builds the converter, reading the configuration file if one is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Converter>` - The converter, or an `Error::Config` if the configuration file is invalid.
+    pub fn build(self) -> Result<Converter> {
+        let mut converter = self.converter;
+
+        // Read the configuration file, unless a configuration is given
+        if converter.config.is_none() {
+            if let Some(config_file) = &converter.config_file {
+                converter.config = Some(read_config_file(config_file)?);
+            }
+        }
+        if let Some(transforms) = self.transforms {
+            converter
+                .config
+                .get_or_insert_with(Default::default)
+                .transforms = transforms;
+        }
+
+        // Fill in the directories and filters from the configuration
+        if let Some(config) = &converter.config {
+            let paths = &config.paths;
+            converter.dest_dir = converter.dest_dir.or_else(|| paths.dest_dir.clone());
+            converter.dest_images_dir = converter
+                .dest_images_dir
+                .or_else(|| paths.dest_images_dir.clone());
+            converter.dest_books_dir = converter
+                .dest_books_dir
+                .or_else(|| paths.dest_books_dir.clone());
+            if converter.include.is_empty() {
+                converter.include = config.filters.include.clone();
+            }
+            if converter.exclude.is_empty() {
+                converter.exclude = config.filters.exclude.clone();
+            }
+            if converter.include_books.is_empty() {
+                converter.include_books = config.filters.books.include.clone();
+            }
+            if converter.exclude_books.is_empty() {
+                converter.exclude_books = config.filters.books.exclude.clone();
+            }
+        }
+
+        // Derive the source directories from the content root
+        let src_dir = self.src_dir.or_else(|| {
+            converter
+                .config
+                .as_ref()
+                .and_then(|config| config.paths.src_dir.clone())
+        });
+        if let Some(src_dir) = src_dir {
+            let join = |dir: &str| Path::new(&src_dir).join(dir).to_string_lossy().to_string();
+            converter.src_articles_dir = converter
+                .src_articles_dir
+                .or_else(|| Some(join(ARTICLES_DIR)));
+            converter.src_images_dir = converter.src_images_dir.or_else(|| Some(join(IMAGES_DIR)));
+            converter.src_books_dir = converter.src_books_dir.or_else(|| Some(join(BOOKS_DIR)));
+        }
+
+        Ok(converter)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::report::{Action, FileStatus};
+    use std::{fs, path::PathBuf};
+    use tempfile::{tempdir, TempDir};
+
+    /// The frontmatter of an article that passes the default validation rules.
+    pub(crate) const SAMPLE_FRONTMATTER: &str =
+        "---\ntitle: Sample\nemoji: '🎉'\ntype: tech\ntopics: []\n---\n";
+
+    /// Creates a Zenn repository in a temporary directory, returning it with its articles directory.
+    pub(crate) fn zenn_repository() -> (TempDir, PathBuf) {
+        let src_dir = tempdir().unwrap();
+        let articles_dir = src_dir.path().join(ARTICLES_DIR);
+        fs::create_dir_all(&articles_dir).unwrap();
+        (src_dir, articles_dir)
+    }
+
+    /// Writes an article with the sample frontmatter and the given body.
+    pub(crate) fn write_article(articles_dir: &Path, name: &str, body: &str) {
+        fs::write(
+            articles_dir.join(name),
+            format!("{}{}", SAMPLE_FRONTMATTER, body),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_build_converter_from_config() {
+        let config: ConfigFile = serde_yaml::from_str(
+            "paths: { src_dir: zenn, dest_dir: docs/entries, dest_images_dir: docs/public }\nfilters: { include: [a] }",
+        )
+        .unwrap();
+
+        let converter = Converter::builder()
+            .config(config)
+            .dest_dir("docs/articles")
+            .build()
+            .unwrap();
+
+        // Values set on the builder win over the configuration
+        assert_eq!(converter.dest_dir.as_deref(), Some("docs/articles"));
+        assert_eq!(converter.dest_images_dir.as_deref(), Some("docs/public"));
+        assert_eq!(converter.src_articles_dir.as_deref(), Some("zenn/articles"));
+        assert_eq!(converter.src_books_dir.as_deref(), Some("zenn/books"));
+        assert_eq!(converter.dest_books_dir, None);
+        assert_eq!(converter.include, vec!["a"]);
+    }
+
+    #[test]
+    fn test_convert_articles_requires_directories() {
+        let converter = Converter::builder().src_dir("zenn").build().unwrap();
+
+        let error = converter.convert_articles().unwrap_err();
+        assert!(error.to_string().contains("`dest_dir` is not set"));
+    }
+
+    #[tokio::test]
+    async fn test_run_converter() {
+        let (src_dir, articles_dir) = zenn_repository();
+        let images_dir = src_dir.path().join(IMAGES_DIR).join("sample");
+        fs::create_dir_all(&images_dir).unwrap();
+        write_article(
+            &articles_dir,
+            "sample-article.md",
+            "![](/images/sample/a.png)\n",
+        );
+        fs::write(images_dir.join("a.png"), "png").unwrap();
+
+        let dest_dir = tempdir().unwrap();
+        let converter = Converter::builder()
+            .src_dir(src_dir.path().to_string_lossy())
+            .dest_dir(dest_dir.path().join("entries").to_string_lossy())
+            .dest_images_dir(dest_dir.path().join("public/images").to_string_lossy())
+            .transforms(TransformOptions {
+                title_heading: crate::markdown::transform::heading::TitleHeading::Never,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        // The destination directories are created by the conversion
+        let report = converter.run().await.unwrap().into_result().unwrap();
+        assert_eq!(report.summary().converted, 2);
+
+        let article =
+            fs::read_to_string(dest_dir.path().join("entries/sample-article.md")).unwrap();
+        assert!(article.ends_with("---\n![](/images/sample/a.png)\n"));
+        assert!(dest_dir.path().join("public/images/sample/a.png").exists());
+
+        // A second run skips the files whose inputs are unchanged
+        let report = converter.run().await.unwrap();
+        assert_eq!(report.summary().unchanged, 2);
+        let manifest = crate::manifest::Manifest::read(&dest_dir.path().join("entries"));
+        assert!(manifest.files.contains_key("sample-article.md"));
+
+        // An edited source or an output modified by hand is converted again
+        fs::write(images_dir.join("a.png"), "png2").unwrap();
+        fs::write(dest_dir.path().join("entries/sample-article.md"), "edited").unwrap();
+        let report = converter.run().await.unwrap();
+        assert_eq!(report.summary().converted, 2);
+        assert_eq!(
+            fs::read_to_string(dest_dir.path().join("public/images/sample/a.png")).unwrap(),
+            "png2"
+        );
+    }
+
+    #[test]
+    fn test_prune_removed_articles() {
+        let (src_dir, articles_dir) = zenn_repository();
+        let dest_dir = tempdir().unwrap();
+        write_article(&articles_dir, "a.md", "Body\n");
+        write_article(&articles_dir, "b.md", "Body\n");
+        fs::write(dest_dir.path().join("index.md"), "# Handwritten").unwrap();
+
+        let converter = Converter::builder()
+            .src_dir(src_dir.path().to_string_lossy())
+            .dest_dir(dest_dir.path().to_string_lossy())
+            .prune(true)
+            .build()
+            .unwrap();
+        assert_eq!(converter.convert_articles().unwrap().summary().converted, 2);
+
+        // The output of the removed article is deleted, but not the handwritten page
+        fs::remove_file(articles_dir.join("b.md")).unwrap();
+        let report = converter.convert_articles().unwrap();
+        let deleted: Vec<_> = report.with_status(FileStatus::Deleted).collect();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].destination, Some(dest_dir.path().join("b.md")));
+        assert!(!dest_dir.path().join("b.md").exists());
+        assert!(dest_dir.path().join("a.md").exists());
+        assert!(dest_dir.path().join("index.md").exists());
+    }
+
+    #[test]
+    fn test_failed_article_keeps_manifest_entry() {
+        let (src_dir, articles_dir) = zenn_repository();
+        let dest_dir = tempdir().unwrap();
+        write_article(&articles_dir, "a.md", "Body\n");
+
+        let converter = Converter::builder()
+            .src_dir(src_dir.path().to_string_lossy())
+            .dest_dir(dest_dir.path().to_string_lossy())
+            .keep_going(true)
+            .prune(true)
+            .build()
+            .unwrap();
+        assert_eq!(converter.convert_articles().unwrap().summary().converted, 1);
+
+        // The entry of the previous run is kept when the article fails to convert
+        fs::write(articles_dir.join("a.md"), "---\ntitle: [\n---\n").unwrap();
+        assert_eq!(converter.convert_articles().unwrap().summary().failed, 1);
+        let manifest = crate::manifest::Manifest::read(dest_dir.path());
+        assert!(manifest.files.contains_key("a.md"));
+
+        // So the output is pruned once the article is removed
+        fs::remove_file(articles_dir.join("a.md")).unwrap();
+        let report = converter.convert_articles().unwrap();
+        assert_eq!(report.summary().deleted, 1);
+        assert!(!dest_dir.path().join("a.md").exists());
+    }
+
+    #[test]
+    fn test_untitled_article_follows_validation_policy() {
+        let (src_dir, articles_dir) = zenn_repository();
+        let dest_dir = tempdir().unwrap();
+        fs::write(
+            articles_dir.join("untitled-article.md"),
+            "---\nemoji: '🎉'\ntype: tech\ntopics: []\n---\nBody\n",
+        )
+        .unwrap();
+        let builder = Converter::builder()
+            .src_dir(src_dir.path().to_string_lossy())
+            .dest_dir(dest_dir.path().to_string_lossy());
+
+        // By default, a missing title fails the article
+        let report = builder.clone().build().unwrap().convert_articles().unwrap();
+        assert_eq!(report.summary().failed, 1);
+        assert_eq!(
+            report.files[0].message.as_deref(),
+            Some("Missing required key `title`")
+        );
+
+        // With the rule turned off, the article is converted
+        let config: ConfigFile = serde_yaml::from_str("validation: { required: off }").unwrap();
+        let report = builder
+            .config(config)
+            .build()
+            .unwrap()
+            .convert_articles()
+            .unwrap();
+        assert_eq!(report.summary().converted, 1);
+        assert!(dest_dir.path().join("untitled-article.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let (src_dir, articles_dir) = zenn_repository();
+        let dest_dir = tempdir().unwrap();
+        let images_dir = src_dir.path().join(IMAGES_DIR);
+        fs::create_dir_all(&images_dir).unwrap();
+        write_article(&articles_dir, "a.md", "New body\n");
+        fs::write(images_dir.join("a.png"), "png").unwrap();
+        fs::write(dest_dir.path().join("a.md"), "Old body\n").unwrap();
+
+        let converter = Converter::builder()
+            .src_dir(src_dir.path().to_string_lossy())
+            .dest_dir(dest_dir.path().to_string_lossy())
+            .dest_images_dir(dest_dir.path().join("images").to_string_lossy())
+            .dry_run(true)
+            .diff(true)
+            .build()
+            .unwrap();
+        let report = converter.run().await.unwrap();
+
+        // The planned changes are reported, but nothing is written
+        assert!(report.dry_run);
+        let actions: Vec<_> = report.files.iter().map(|file| file.action).collect();
+        assert_eq!(actions, vec![Some(Action::Update), Some(Action::Create)]);
+        assert!(report.files[0]
+            .diff
+            .as_deref()
+            .unwrap()
+            .contains("-Old body\n"));
+        assert_eq!(
+            fs::read_to_string(dest_dir.path().join("a.md")).unwrap(),
+            "Old body\n"
+        );
+        assert!(!dest_dir.path().join("images").exists());
+        assert!(!dest_dir
+            .path()
+            .join(crate::manifest::MANIFEST_FILE)
+            .exists());
+    }
+}
@@ -0,0 +1,297 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::markdown::validation::ValidationIssue;
+
+/// A specialized `Result` type for zenn2press operations.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// `Error` is the error type returned by the public functions of zenn2press.
+///
+/// Each variant carries the path of the offending file and, where it is known, the line.
+/// The path is empty when the function that raised the error was not given one
+/// (e.g. [`parse_frontmatter`](crate::markdown::frontmatter::parse_frontmatter)),
+/// and can be filled in with [`Error::with_path`].
+#[derive(Debug)]
+pub enum Error {
+    /// The configuration file (or a book's `config.yaml`) could not be parsed or has invalid values.
+    Config {
+        /// The configuration file.
+        path: PathBuf,
+        /// The 1-based line of the error, if known.
+        line: Option<usize>,
+        /// A description of the problem.
+        message: String,
+    },
+    /// The frontmatter of a Markdown file is not valid YAML.
+    Frontmatter {
+        /// The Markdown file.
+        path: PathBuf,
+        /// The 1-based line of the error in the file, if known.
+        line: Option<usize>,
+        /// The 1-based column of the error, if known.
+        column: Option<usize>,
+        /// The message of the YAML parser.
+        message: String,
+    },
+    /// A file or directory could not be read or written.
+    Io {
+        /// The file or directory.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: io::Error,
+    },
+    /// Articles broke validation rules whose policy is `error`.
+    Validation {
+        /// The failed articles, each with its error-level issues.
+        failures: Vec<(PathBuf, Vec<ValidationIssue>)>,
+    },
+    /// Files failed to convert, as listed by [`ConversionReport::into_result`](crate::ConversionReport::into_result).
+    Files {
+        /// The failed files, each with its error.
+        failures: Vec<(PathBuf, Error)>,
+    },
+    /// A file could not be converted or its output could not be serialized.
+    Transform {
+        /// The file being converted or written.
+        path: PathBuf,
+        /// The 1-based line of the error, if known.
+        line: Option<usize>,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl Error {
+    /// Creates an `Error::Io` for the given path.
+    pub(crate) fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Creates an `Error::Config` without a line.
+    pub(crate) fn config(path: impl Into<PathBuf>, message: impl fmt::Display) -> Self {
+        Error::Config {
+            path: path.into(),
+            line: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates an `Error::Transform` without a line.
+    pub(crate) fn transform(path: impl Into<PathBuf>, message: impl fmt::Display) -> Self {
+        Error::Transform {
+            path: path.into(),
+            line: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the path of the offending file, or the first failed article for validation errors.
+    pub fn path(&self) -> Option<&Path> {
+        let path = match self {
+            Error::Config { path, .. }
+            | Error::Frontmatter { path, .. }
+            | Error::Io { path, .. }
+            | Error::Transform { path, .. } => path.as_path(),
+            Error::Validation { failures } => failures.first()?.0.as_path(),
+            Error::Files { failures } => failures.first()?.0.as_path(),
+        };
+        (!path.as_os_str().is_empty()).then_some(path)
+    }
+
+    /// Returns the 1-based line of the error, if known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Error::Config { line, .. }
+            | Error::Frontmatter { line, .. }
+            | Error::Transform { line, .. } => *line,
+            Error::Io { .. } | Error::Validation { .. } | Error::Files { .. } => None,
+        }
+    }
+
+    /// Sets the path of the error if it has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path of the offending file.
+    ///
+    /// # Returns
+    ///
+    /// * `Error` - The error with its path set.
+    pub fn with_path(mut self, file: impl AsRef<Path>) -> Self {
+        match &mut self {
+            Error::Config { path, .. }
+            | Error::Frontmatter { path, .. }
+            | Error::Io { path, .. }
+            | Error::Transform { path, .. } => {
+                if path.as_os_str().is_empty() {
+                    *path = file.as_ref().to_path_buf();
+                }
+            }
+            Error::Validation { .. } | Error::Files { .. } => {}
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config {
+                path,
+                line,
+                message,
+            } => {
+                write!(f, "Invalid configuration file {:?}", path)?;
+                if let Some(line) = line {
+                    write!(f, " at line {}", line)?;
+                }
+                write!(f, ": {}", message)
+            }
+            Error::Frontmatter {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "Invalid frontmatter in {:?}", path)?;
+                match (line, column) {
+                    (Some(line), Some(column)) => {
+                        write!(f, " at line {}, column {}", line, column)?
+                    }
+                    (Some(line), None) => write!(f, " at line {}", line)?,
+                    _ => {}
+                }
+                write!(f, ": {}", message)
+            }
+            Error::Io { path, source } => write!(f, "{:?}: {}", path, source),
+            Error::Validation { failures } => {
+                write!(f, "{} article(s) failed validation", failures.len())?;
+                for (path, issues) in failures {
+                    for issue in issues {
+                        write!(
+                            f,
+                            "\n\t- {:?} ({})",
+                            path.file_name().unwrap_or_default(),
+                            issue
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+            Error::Files { failures } => {
+                write!(f, "{} file(s) failed", failures.len())?;
+                for (path, error) in failures {
+                    // Indent the lines of multi-line errors below their file
+                    write!(
+                        f,
+                        "\n\t- {:?}: {}",
+                        path.file_name().unwrap_or_default(),
+                        error.to_string().replace('\n', "\n\t")
+                    )?;
+                }
+                Ok(())
+            }
+            Error::Transform {
+                path,
+                line,
+                message,
+            } => {
+                write!(f, "Failed to convert {:?}", path)?;
+                if let Some(line) = line {
+                    write!(f, " at line {}", line)?;
+                }
+                write!(f, ": {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Adds the offending path to I/O errors.
+pub(crate) trait IoResultExt<T> {
+    /// Converts an `io::Result` into a `Result` with an `Error::Io` for the given path.
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T>;
+}
+
+impl<T> IoResultExt<T> for io::Result<T> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T> {
+        self.map_err(|source| Error::io(path.as_ref(), source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::validation::ValidationPolicy;
+
+    #[test]
+    fn test_error_display() {
+        let error = Error::Frontmatter {
+            path: PathBuf::from("articles/a.md"),
+            line: Some(3),
+            column: Some(7),
+            message: "did not find expected node content".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid frontmatter in \"articles/a.md\" at line 3, column 7: did not find expected node content"
+        );
+        assert_eq!(error.line(), Some(3));
+
+        let error = Error::Validation {
+            failures: vec![(
+                PathBuf::from("articles/a.md"),
+                vec![ValidationIssue {
+                    policy: ValidationPolicy::Error,
+                    message: "Missing required key `title`".to_string(),
+                }],
+            )],
+        };
+        assert_eq!(
+            error.to_string(),
+            "1 article(s) failed validation\n\t- \"a.md\" (Missing required key `title`)"
+        );
+        assert_eq!(error.path(), Some(Path::new("articles/a.md")));
+
+        let error = Error::Files {
+            failures: vec![
+                (
+                    PathBuf::from("articles/b.md"),
+                    Error::transform("articles/b.md", "invalid YAML"),
+                ),
+                (PathBuf::from("articles/a.md"), error),
+            ],
+        };
+        assert_eq!(
+            error.to_string(),
+            "2 file(s) failed\n\t- \"b.md\": Failed to convert \"articles/b.md\": invalid YAML\n\t- \"a.md\": 1 article(s) failed validation\n\t\t- \"a.md\" (Missing required key `title`)"
+        );
+        assert_eq!(error.path(), Some(Path::new("articles/b.md")));
+    }
+
+    #[test]
+    fn test_error_with_path() {
+        let error = Error::config("", "Invalid `now` date").with_path("config.yaml");
+        assert_eq!(error.path(), Some(Path::new("config.yaml")));
+
+        // An existing path is kept
+        let error = error.with_path("other.yaml");
+        assert_eq!(error.path(), Some(Path::new("config.yaml")));
+
+        let error: Result<()> = Err(io::Error::from(io::ErrorKind::NotFound)).with_path("missing");
+        assert!(matches!(error, Err(Error::Io { .. })));
+    }
+}
@@ -1,25 +1,50 @@
 use std::fs;
-use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use crate::error::{IoResultExt, Result};
+use crate::manifest::{hash_bytes, manifest_key, Manifest, ManifestEntry};
+use crate::output::OutputMode;
 use crate::progress_bar::get_pb;
+use crate::report::{Action, ConversionReport, FileKind, FileReport, FileStatus};
 
 /// Copies files to a target directory.
 ///
 /// This function iterates over a list of file paths, determines their relative paths
 /// with respect to a source directory, and copies them to a destination directory while
-/// maintaining the directory structure.
+/// maintaining the directory structure. Files recorded as unchanged in the
+/// [`Manifest`] of the destination directory, or whose destination already has the same
+/// content, are left alone. In keep-going mode, a file that cannot be copied does not stop
+/// the others. In dry-run mode, the files are reported but not copied.
 ///
 /// # Arguments
 ///
 /// * `file_paths` - A slice of `PathBuf` containing the paths of files to copy.
 /// * `src_dir` - The source directory as a string slice.
 /// * `dest_dir` - The destination directory as a string slice.
+/// * `keep_going` - Whether to keep copying the other files when one fails.
+/// * `mode` - How the files are written.
 ///
 /// # Returns
 ///
-/// * `io::Result<()>` - Returns `Ok(())` on success or an `io::Error` on failure.
-pub fn copy_files(file_paths: &[PathBuf], src_dir: &str, dest_dir: &str) -> io::Result<()> {
+/// * `Result<ConversionReport>` - The outcome of every file, or an `Error::Io` naming the
+///   offending file on failure outside keep-going mode.
+pub fn copy_files(
+    file_paths: &[PathBuf],
+    src_dir: &str,
+    dest_dir: &str,
+    keep_going: bool,
+    mode: OutputMode,
+) -> Result<ConversionReport> {
+    let started = Instant::now();
+    let mut conversion = ConversionReport {
+        dry_run: mode.dry_run,
+        ..Default::default()
+    };
+
+    // Read the manifest of the files copied by previous runs
+    let mut manifest = Manifest::read(Path::new(dest_dir));
+
     let file_length = file_paths.len().try_into().unwrap();
 
     let pb = get_pb(file_length);
@@ -28,28 +53,110 @@ pub fn copy_files(file_paths: &[PathBuf], src_dir: &str, dest_dir: &str) -> io::
         let filename = file.file_name().unwrap().to_string_lossy().to_string();
         pb.set_message(filename);
 
-        // Determine the file's relative path from the source directory
-        let relative_path = file.strip_prefix(src_dir).unwrap();
-        let target_path = Path::new(dest_dir).join(relative_path);
-
-        // Create the necessary directory structure in the destination
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        // Copy the file to the destination
-        fs::copy(file, target_path)?;
+        // Copy the file, recording the failure in keep-going mode
+        let file_started = Instant::now();
+        let entry = match copy_file(file, src_dir, dest_dir, mode, &mut manifest) {
+            Ok(entry) => entry,
+            Err(e) if keep_going => FileReport::failed(FileKind::Image, file, e),
+            Err(e) => return Err(e),
+        };
+        conversion.push(entry.timed(file_started));
 
         pb.inc(1)
     }
 
     pb.finish_with_message("Completed.");
-    Ok(())
+
+    // Record the copied files for the next run
+    if !mode.dry_run {
+        manifest.write(Path::new(dest_dir))?;
+    }
+
+    Ok(conversion.timed(started))
+}
+
+/// Copies a single file to the destination directory, keeping its path relative to the source directory.
+///
+/// The entry of the file in the manifest is updated once it is copied. If the copy fails, the entry
+/// of the previous run is kept so that its output can still be pruned.
+fn copy_file(
+    file: &Path,
+    src_dir: &str,
+    dest_dir: &str,
+    mode: OutputMode,
+    manifest: &mut Manifest,
+) -> Result<FileReport> {
+    // Determine the file's relative path from the source directory
+    let relative_path = file.strip_prefix(src_dir).unwrap();
+    let target_path = Path::new(dest_dir).join(relative_path);
+
+    // Skip the file if it is unchanged since the previous run
+    let key = manifest_key(Path::new(dest_dir), &target_path);
+    let source_hash = hash_bytes(&fs::read(file).with_path(file)?);
+    let previous = manifest.files.get(&key);
+    if previous.is_some_and(|e| e.is_unchanged(&source_hash, None, &target_path)) {
+        let mut entry = FileReport::new(FileKind::Image, FileStatus::Unchanged, file);
+        entry.destination = Some(target_path);
+        entry.action = Some(Action::Unchanged);
+        return Ok(entry);
+    }
+
+    // Copy the file to the destination, unless it already has the same content
+    let action = if is_same_content(file, &target_path)? {
+        Action::Unchanged
+    } else if target_path.exists() {
+        Action::Update
+    } else {
+        Action::Create
+    };
+    let mut entry = if action == Action::Unchanged {
+        FileReport::new(FileKind::Image, FileStatus::Unchanged, file)
+    } else {
+        let bytes = if mode.dry_run {
+            fs::metadata(file).with_path(file)?.len()
+        } else {
+            // Create the necessary directory structure in the destination
+            if let Some(parent) = target_path.parent() {
+                fs::create_dir_all(parent).with_path(parent)?;
+            }
+            fs::copy(file, &target_path).with_path(&target_path)?
+        };
+        FileReport {
+            bytes: Some(bytes),
+            ..FileReport::new(FileKind::Image, FileStatus::Converted, file)
+        }
+    };
+    entry.destination = Some(target_path);
+    entry.action = Some(action);
+
+    // The copy has the same content as the source
+    let output_hash = source_hash.clone();
+    manifest.files.insert(
+        key,
+        ManifestEntry::new(file, source_hash, None, output_hash),
+    );
+
+    Ok(entry)
+}
+
+/// Returns whether the destination file exists with the same content as the source file.
+fn is_same_content(file: &Path, target_path: &Path) -> Result<bool> {
+    // Compare the sizes first to avoid reading files that differ
+    let Ok(target) = fs::metadata(target_path) else {
+        return Ok(false);
+    };
+    let source = fs::metadata(file).with_path(file)?;
+    if source.len() != target.len() {
+        return Ok(false);
+    }
+
+    Ok(fs::read(file).with_path(file)? == fs::read(target_path).with_path(target_path)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Error;
     use std::fs::{self, File};
     use std::io::Write;
 
@@ -74,12 +181,16 @@ mod tests {
 
         // Test copying files
         let file_paths = vec![file1.clone(), file2.clone()];
-        copy_files(
+        let report = copy_files(
             &file_paths,
             src_dir.to_str().unwrap(),
             dest_dir.to_str().unwrap(),
+            false,
+            OutputMode::default(),
         )
         .unwrap();
+        assert_eq!(report.summary().converted, 2);
+        assert_eq!(report.summary().bytes, 24);
 
         let copied_file1 = dest_dir.join("file1.txt");
         let copied_file2 = dest_dir.join("file2.txt");
@@ -93,5 +204,45 @@ mod tests {
 
         assert_eq!(contents1.trim(), "Test file 1");
         assert_eq!(contents2.trim(), "Test file 2");
+
+        // Files that are already up to date are not copied again
+        let report = copy_files(
+            &file_paths,
+            src_dir.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            false,
+            OutputMode::default(),
+        )
+        .unwrap();
+        assert_eq!(report.summary().unchanged, 2);
+        assert_eq!(report.summary().bytes, 0);
+    }
+
+    #[test]
+    fn test_copy_files_keep_going() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let file = src_dir.join("file.txt");
+        let missing = src_dir.join("missing.txt");
+        fs::write(&file, "Test file").unwrap();
+
+        // The missing file stops the copy, or is reported after the others in keep-going mode
+        let file_paths = vec![missing.clone(), file.clone()];
+        let src = src_dir.to_str().unwrap();
+        let dest = dest_dir.to_str().unwrap();
+        assert!(matches!(
+            copy_files(&file_paths, src, dest, false, OutputMode::default()),
+            Err(Error::Io { .. })
+        ));
+        assert!(!dest_dir.join("file.txt").exists());
+
+        let report = copy_files(&file_paths, src, dest, true, OutputMode::default()).unwrap();
+        let failed: Vec<&FileReport> = report.with_status(FileStatus::Failed).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].source, missing);
+        assert!(dest_dir.join("file.txt").exists());
     }
 }
@@ -1,8 +1,9 @@
 use futures::future::BoxFuture;
 use std::fs;
-use std::io;
 use std::path::PathBuf;
 
+use crate::error::{IoResultExt, Result};
+
 /// Recursively retrieves all file paths in a directory, with optional include and exclude filters.
 ///
 /// This function traverses a directory and returns a vector of file paths. It can optionally
@@ -16,18 +17,18 @@ use std::path::PathBuf;
 ///
 /// # Returns
 ///
-/// * `BoxFuture<'a, io::Result<Vec<PathBuf>>>` - A future that resolves to a vector of file paths.
+/// * `BoxFuture<'a, Result<Vec<PathBuf>>>` - A future that resolves to a vector of file paths, or an `Error::Io` naming the directory that cannot be read.
 pub fn get_file_paths<'a>(
     dir_path: &'a str,
     include: Option<Vec<&'a str>>,
     exclude: Option<Vec<&'a str>>,
-) -> BoxFuture<'a, io::Result<Vec<PathBuf>>> {
+) -> BoxFuture<'a, Result<Vec<PathBuf>>> {
     Box::pin(async move {
         let mut entries: Vec<PathBuf> = Vec::new();
-        let dir = fs::read_dir(dir_path)?;
+        let dir = fs::read_dir(dir_path).with_path(dir_path)?;
 
         for entry in dir {
-            let entry = entry?;
+            let entry = entry.with_path(dir_path)?;
             let path = entry.path();
             let path_str = path.to_str().unwrap_or_default();
 
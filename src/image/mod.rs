@@ -1,13 +1,20 @@
-use std::io;
-
 use copy::copy_files;
 use file::get_file_paths;
 
+use crate::converter::Converter;
+use crate::error::Result;
+use crate::manifest::prune_outputs;
+use crate::report::{ConversionReport, FileKind};
+
 pub mod copy;
 pub mod file;
 
 /// This function copies all files and directories from the source directory to the destination directory.
 ///
+/// It is a shim over [`Converter::convert_images`], kept for compatibility. The keep-going
+/// mode and other options are only available on the builder. Files whose destination already
+/// has the same content are not copied again, and the copy stops at the first file that fails.
+///
 /// # Arguments
 ///
 /// * `src_dir` - The source directory path.
@@ -18,10 +25,11 @@ pub mod file;
 /// # Examples
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// use zenn2press::copy_images;
 ///
 /// #[tokio::main]
-/// async fn main() -> std::io::Result<()> {
+/// async fn main() -> zenn2press::Result<()> {
 ///     let src_dir = "demo/zenn/images";
 ///     let dest_dir = "demo/press/docs/public/images";
 ///     let include = Some(vec!["sample-article-1"]);
@@ -31,14 +39,54 @@ pub mod file;
 ///     Ok(())
 /// }
 /// ```
+#[deprecated(note = "use `Converter::builder()` and `Converter::convert_images` instead")]
 pub async fn copy_images(
     src_dir: &str,
     dest_dir: &str,
     include: Option<Vec<&str>>,
     exclude: Option<Vec<&str>>,
-) -> io::Result<()> {
-    // Get all file paths from the source directory
-    let file_paths = get_file_paths(src_dir, include, exclude).await?;
+) -> Result<()> {
+    Converter::builder()
+        .src_images_dir(src_dir)
+        .dest_images_dir(dest_dir)
+        .include(include.unwrap_or_default())
+        .exclude(exclude.unwrap_or_default())
+        .build()?
+        .convert_images()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+/// Copies the images of a converter from the source directory to the destination directory.
+///
+/// # Arguments
+///
+/// * `converter` - The converter holding the filters.
+/// * `src_dir` - The Zenn images directory.
+/// * `dest_dir` - The VitePress directory of the images.
+///
+/// # Returns
+///
+/// * `Result<ConversionReport>` - The outcome of every image.
+pub(crate) async fn convert_images(
+    converter: &Converter,
+    src_dir: &str,
+    dest_dir: &str,
+) -> Result<ConversionReport> {
+    // Get all file paths from the source directory, in a stable order for the report
+    let mut file_paths = get_file_paths(src_dir, converter.include(), converter.exclude()).await?;
+    file_paths.sort();
     // Copy each file to the destination directory
-    copy_files(&file_paths, src_dir, dest_dir)
+    let mode = converter.output_mode();
+    let mut report = copy_files(&file_paths, src_dir, dest_dir, converter.keep_going, mode)?;
+
+    // Delete the images whose source was removed, if requested
+    if converter.prune {
+        for pruned in prune_outputs(src_dir, dest_dir, FileKind::Image, mode)? {
+            report.push(pruned);
+        }
+    }
+
+    Ok(report)
 }
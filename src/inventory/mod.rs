@@ -0,0 +1,359 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+use crate::book::{chapter::get_chapters, config::read_book_config};
+use crate::converter::Converter;
+use crate::error::{Error, IoResultExt, Result};
+use crate::image::file::get_file_paths;
+use crate::markdown::{
+    filter::filter_markdown_files,
+    frontmatter::{parse_frontmatter, Frontmatter},
+};
+
+/// `Inventory` lists the Zenn content a converter would read, with its metadata.
+///
+/// Unlike a conversion, it applies the include and exclude lists of the articles,
+/// books and images but not the publication policy, so drafts are listed too.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Inventory {
+    /// The articles, ordered by file name.
+    pub articles: Vec<ArticleEntry>,
+    /// The books, ordered by slug.
+    pub books: Vec<BookEntry>,
+    /// The images, ordered by path.
+    pub images: Vec<ImageEntry>,
+}
+
+/// `ArticleEntry` is a Zenn article with the metadata of its frontmatter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ArticleEntry {
+    /// The path of the article.
+    pub source: PathBuf,
+    /// The slug of the article, i.e. its file name without `.md`.
+    pub slug: String,
+    /// The `title` of the article.
+    pub title: Option<String>,
+    /// The `emoji` of the article.
+    pub emoji: Option<String>,
+    /// The `type` of the article (`tech` or `idea`).
+    #[serde(rename = "type")]
+    pub article_type: Option<String>,
+    /// The `topics` of the article.
+    pub topics: Vec<String>,
+    /// Whether the article is `published`.
+    pub published: Option<bool>,
+    /// The `published_at` date of the article, as written.
+    pub published_at: Option<String>,
+    /// The error reading the article, if it cannot be read or its frontmatter is invalid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `BookEntry` is a Zenn book with the metadata of its `config.yaml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BookEntry {
+    /// The directory of the book.
+    pub source: PathBuf,
+    /// The slug of the book, i.e. its directory name.
+    pub slug: String,
+    /// The `title` of the book.
+    pub title: Option<String>,
+    /// The `topics` of the book.
+    pub topics: Vec<String>,
+    /// Whether the book is `published`.
+    pub published: Option<bool>,
+    /// The chapter slugs in reading order.
+    pub chapters: Vec<String>,
+    /// The error reading the book, if its configuration or chapters cannot be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `ImageEntry` is an image of the Zenn repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImageEntry {
+    /// The path of the image.
+    pub source: PathBuf,
+    /// The path of the image below the images directory.
+    pub path: PathBuf,
+    /// The size of the image in bytes.
+    pub bytes: u64,
+}
+
+impl Inventory {
+    /// Serializes the inventory into pretty-printed JSON.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String>` - The JSON text, or an `Error::Transform` if it cannot be serialized.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::transform("", e))
+    }
+}
+
+impl fmt::Display for Inventory {
+    /// Formats the inventory as text, with a section per kind of content and a line per entry.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Articles ({}):", self.articles.len())?;
+        for article in &self.articles {
+            write!(f, "\n\t{}", article.slug)?;
+            if let Some(error) = &article.error {
+                write!(f, ": error: {}", error)?;
+                continue;
+            }
+            write!(
+                f,
+                ": {:?} ({}{}, {}",
+                article.title.as_deref().unwrap_or_default(),
+                article
+                    .emoji
+                    .as_deref()
+                    .map(|emoji| format!("{} ", emoji))
+                    .unwrap_or_default(),
+                article.article_type.as_deref().unwrap_or("no type"),
+                publication(article.published)
+            )?;
+            if let Some(published_at) = &article.published_at {
+                write!(f, " at {}", published_at)?;
+            }
+            if !article.topics.is_empty() {
+                write!(f, ", topics: {}", article.topics.join(", "))?;
+            }
+            write!(f, ")")?;
+        }
+
+        write!(f, "\nBooks ({}):", self.books.len())?;
+        for book in &self.books {
+            write!(f, "\n\t{}", book.slug)?;
+            if let Some(error) = &book.error {
+                write!(f, ": error: {}", error)?;
+                continue;
+            }
+            write!(
+                f,
+                ": {:?} ({} chapter(s), {}",
+                book.title.as_deref().unwrap_or_default(),
+                book.chapters.len(),
+                publication(book.published)
+            )?;
+            if !book.topics.is_empty() {
+                write!(f, ", topics: {}", book.topics.join(", "))?;
+            }
+            write!(f, ")")?;
+        }
+
+        write!(f, "\nImages ({}):", self.images.len())?;
+        for image in &self.images {
+            write!(f, "\n\t{}: {} bytes", image.path.display(), image.bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the publication state shown for a `published` flag.
+fn publication(published: Option<bool>) -> &'static str {
+    if published == Some(true) {
+        "published"
+    } else {
+        "draft"
+    }
+}
+
+/// Lists the articles, books and images of a converter.
+///
+/// The articles directory must exist. The books and images are listed only if
+/// their directories exist, as a Zenn repository may have neither.
+///
+/// # Arguments
+///
+/// * `converter` - The converter holding the source directories and the filters.
+/// * `src_dir` - The Zenn articles directory.
+///
+/// # Returns
+///
+/// * `Result<Inventory>` - The content found, or an `Error::Io` if a directory cannot be read.
+pub(crate) async fn list(converter: &Converter, src_dir: &str) -> Result<Inventory> {
+    let mut inventory = Inventory::default();
+
+    // List the articles, reading their frontmatter
+    let mut files: Vec<PathBuf> = fs::read_dir(src_dir)
+        .with_path(src_dir)?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    files.sort();
+    for file in filter_markdown_files(files, converter.include(), converter.exclude()) {
+        inventory.articles.push(article_entry(file));
+    }
+
+    // List the books, reading their configuration
+    if let Some(books_dir) = existing_dir(&converter.src_books_dir) {
+        let mut book_dirs: Vec<PathBuf> = fs::read_dir(books_dir)
+            .with_path(books_dir)?
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.join("config.yaml").is_file())
+            .collect();
+        book_dirs.sort();
+        book_dirs
+            .retain(|dir| converter.is_book_selected(&dir.file_name().unwrap().to_string_lossy()));
+        for book_dir in book_dirs {
+            inventory.books.push(book_entry(book_dir));
+        }
+    }
+
+    // List the images with their size
+    if let Some(images_dir) = existing_dir(&converter.src_images_dir) {
+        let mut file_paths =
+            get_file_paths(images_dir, converter.include(), converter.exclude()).await?;
+        file_paths.sort();
+        for source in file_paths {
+            let bytes = fs::metadata(&source).with_path(&source)?.len();
+            let path = source.strip_prefix(images_dir).unwrap_or(&source).into();
+            inventory.images.push(ImageEntry {
+                source,
+                path,
+                bytes,
+            });
+        }
+    }
+
+    Ok(inventory)
+}
+
+/// Returns a directory if it is set and exists.
+fn existing_dir(dir: &Option<String>) -> Option<&str> {
+    dir.as_deref().filter(|dir| Path::new(dir).is_dir())
+}
+
+/// Reads the metadata of an article, recording the error if it cannot be read.
+fn article_entry(source: PathBuf) -> ArticleEntry {
+    let slug = source.file_stem().unwrap().to_string_lossy().to_string();
+    let frontmatter = fs::read_to_string(&source)
+        .with_path(&source)
+        .and_then(|content| parse_frontmatter(&content).map_err(|e| e.with_path(&source)));
+    let data = match frontmatter {
+        Ok((_, data)) => data,
+        Err(e) => {
+            return ArticleEntry {
+                source,
+                slug,
+                error: Some(e.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+
+    ArticleEntry {
+        title: string_value(&data, "title"),
+        emoji: string_value(&data, "emoji"),
+        article_type: string_value(&data, "type"),
+        topics: data
+            .get("topics")
+            .and_then(Value::as_sequence)
+            .map(|topics| {
+                topics
+                    .iter()
+                    .filter_map(|topic| topic.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        published: data.get("published").and_then(Value::as_bool),
+        published_at: string_value(&data, "published_at"),
+        source,
+        slug,
+        error: None,
+    }
+}
+
+/// Returns a frontmatter value as a string, if it is a string.
+fn string_value(data: &Frontmatter, key: &str) -> Option<String> {
+    data.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Reads the metadata of a book, recording the error if it cannot be read.
+fn book_entry(source: PathBuf) -> BookEntry {
+    let slug = source.file_name().unwrap().to_string_lossy().to_string();
+    let book = read_book_config(&source.join("config.yaml")).and_then(|config| {
+        let chapters = get_chapters(&source, &config, &mut Vec::new())?;
+        Ok((config, chapters))
+    });
+
+    match book {
+        Ok((config, chapters)) => BookEntry {
+            title: Some(config.title),
+            topics: config.topics,
+            published: config.published,
+            chapters: chapters.into_iter().map(|chapter| chapter.slug).collect(),
+            source,
+            slug,
+            error: None,
+        },
+        Err(e) => BookEntry {
+            source,
+            slug,
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_list_content() {
+        let dir = tempdir().unwrap();
+        let src = dir.path();
+        fs::create_dir_all(src.join("articles")).unwrap();
+        fs::write(
+            src.join("articles/a.md"),
+            "---\ntitle: \"A\"\nemoji: \"📝\"\ntype: \"tech\"\ntopics: [\"rust\"]\npublished: false\n---\nBody\n",
+        )
+        .unwrap();
+        fs::write(src.join("articles/broken.md"), "---\ntitle: [\n---\n").unwrap();
+        fs::create_dir_all(src.join("books/b")).unwrap();
+        fs::write(
+            src.join("books/b/config.yaml"),
+            "title: \"B\"\npublished: true\nchapters:\n  - intro\n",
+        )
+        .unwrap();
+        fs::write(src.join("books/b/intro.md"), "---\ntitle: \"Intro\"\n---\n").unwrap();
+        fs::create_dir_all(src.join("images/a")).unwrap();
+        fs::write(src.join("images/a/image.png"), "png").unwrap();
+
+        let converter = Converter::builder()
+            .src_dir(src.to_string_lossy())
+            .build()
+            .unwrap();
+        let inventory = converter.list().await.unwrap();
+
+        // Drafts are listed with their metadata, and unreadable articles with their error
+        assert_eq!(inventory.articles.len(), 2);
+        let article = &inventory.articles[0];
+        assert_eq!(article.slug, "a");
+        assert_eq!(article.title.as_deref(), Some("A"));
+        assert_eq!(article.article_type.as_deref(), Some("tech"));
+        assert_eq!(article.topics, vec!["rust"]);
+        assert_eq!(article.published, Some(false));
+        assert!(inventory.articles[1].error.is_some());
+
+        assert_eq!(inventory.books[0].slug, "b");
+        assert_eq!(inventory.books[0].chapters, vec!["intro"]);
+        assert_eq!(inventory.images[0].path, Path::new("a/image.png"));
+        assert_eq!(inventory.images[0].bytes, 3);
+
+        let text = inventory.to_string();
+        assert!(text.contains("\ta: \"A\" (📝 tech, draft, topics: rust)"));
+        assert!(text.contains("\tb: \"B\" (1 chapter(s), published)"));
+        assert!(text.contains("\ta/image.png: 3 bytes"));
+        assert!(inventory.to_json().unwrap().contains("\"type\": \"tech\""));
+    }
+}
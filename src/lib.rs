@@ -1,6 +1,22 @@
+pub mod book;
+pub mod converter;
+pub mod error;
 pub mod image;
+pub mod inventory;
+pub mod manifest;
 pub mod markdown;
+pub mod output;
 pub mod progress_bar;
+pub mod report;
+pub mod sidebar;
+pub mod watch;
 
+#[allow(deprecated)]
+pub use book::copy_books;
+pub use converter::{Converter, ConverterBuilder};
+pub use error::{Error, Result};
+#[allow(deprecated)]
 pub use image::copy_images;
+#[allow(deprecated)]
 pub use markdown::copy_markdown_files;
+pub use report::ConversionReport;
@@ -1,10 +1,14 @@
 use std::time::Instant;
 
-use colored::Colorize;
-use indicatif::HumanDuration;
-
-use utils::{figlet::print_figlet, property::get_properties};
-use zenn2press::{image::copy_images, markdown::copy_markdown_files};
+use utils::{
+    convert::{check, convert, converter_builder},
+    figlet::print_figlet,
+    init::init,
+    list::list,
+    property::{get_task, Task},
+    set_progress_to_stderr,
+    watch::watch,
+};
 
 mod constants;
 mod utils;
@@ -14,62 +18,42 @@ async fn main() {
     // Record the start time of the process
     let started = Instant::now();
 
-    // Print Figlet text
-    print_figlet();
-
-    // Fetch properties from the configuration
-    let properties = get_properties();
-
-    // Convert Vec<String> to Vec<&str> for include and exclude lists
-    let include: Option<Vec<&str>> = properties
-        .include
-        .as_ref()
-        .map(|v| v.iter().map(|s| s.as_str()).collect());
-    let exclude: Option<Vec<&str>> = properties
-        .exclude
-        .as_ref()
-        .map(|v| v.iter().map(|s| s.as_str()).collect());
-
-    // Print status update for copying markdown files
-    println!(
-        "{} {} {}",
-        "[2/3]".bright_black().bold(),
-        "📚",
-        "Copy markdown files:".bold().underline()
-    );
-
-    // Copy markdown files from srcDir to destDir using properties
-    // If an error occurs, print the error message
-    copy_markdown_files(
-        properties.src_articles_dir.as_str(),
-        properties.dest_articles_dir.as_str(),
-        properties.config_file.as_deref(),
-        include.clone(),
-        exclude.clone(),
-    )
-    .unwrap_or_else(|e| {
-        eprintln!("Error copying markdown files: {}", e);
-    });
-
-    // Print status update for copying image files
-    println!(
-        "{} {} {}",
-        "[3/3]".bright_black().bold(),
-        "🖼️",
-        "Copy image files:".bold().underline()
-    );
-
-    // Copy images from srcDir to destDir using properties
-    // Await the result and handle success or error
-    match copy_images(
-        properties.src_images_dir.as_str(),
-        properties.dest_images_dir.as_str(),
-        include.clone(),
-        exclude.clone(),
-    )
-    .await
-    {
-        Ok(_) => println!("\n\n{} Done in {}", "✨", HumanDuration(started.elapsed())),
-        Err(e) => eprintln!("Error copying images: {}", e),
+    // Parse the subcommand and its properties
+    let task = get_task();
+
+    // Run the subcommand, recording whether it failed
+    let failed = match task {
+        Task::Convert(properties) => {
+            // Keep the standard output for the report, if it is printed there
+            set_progress_to_stderr(properties.report.is_some() && properties.report_file.is_none());
+
+            // Print Figlet text
+            print_figlet();
+            convert(&properties, started).await
+        }
+        Task::Watch(properties) => {
+            // Print Figlet text
+            print_figlet();
+
+            // Convert everything, then keep converting the files that change
+            let failed = convert(&properties, started).await;
+            watch(converter_builder(&properties)).await;
+            failed
+        }
+        Task::Check(properties) => {
+            // Keep the standard output for the report, if it is printed there
+            set_progress_to_stderr(properties.report.is_some() && properties.report_file.is_none());
+
+            // Print Figlet text
+            print_figlet();
+            check(properties, started).await
+        }
+        Task::List(properties) => list(properties).await,
+        Task::Init(properties) => init(properties),
+    };
+
+    // Exit with a non-zero status if any step failed
+    if failed {
+        std::process::exit(1);
     }
 }
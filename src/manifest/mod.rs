@@ -0,0 +1,409 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, IoResultExt, Result};
+use crate::output::OutputMode;
+use crate::report::{Action, FileKind, FileReport, FileStatus};
+
+/// The name of the manifest file written in each destination directory.
+pub const MANIFEST_FILE: &str = ".zenn2press-manifest.json";
+
+/// The version of zenn2press recorded in the manifest.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `ManifestEntry` records the inputs and the output of a file written by zenn2press.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// The source file.
+    pub source: PathBuf,
+    /// The SHA-256 hash of the source file.
+    pub source_hash: String,
+    /// The SHA-256 hash of the settings the output depends on, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_hash: Option<String>,
+    /// The version of zenn2press that wrote the output.
+    pub version: String,
+    /// The SHA-256 hash of the output file.
+    pub output_hash: String,
+    /// The warnings raised when the output was written, reported again while it is unchanged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl ManifestEntry {
+    /// Creates an entry for an output written by the running version of zenn2press.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source file.
+    /// * `source_hash` - The hash of the source file.
+    /// * `config_hash` - The hash of the settings the output depends on, if any.
+    /// * `output_hash` - The hash of the output file.
+    ///
+    /// # Returns
+    ///
+    /// * `ManifestEntry` - The entry, without warnings.
+    pub fn new(
+        source: impl Into<PathBuf>,
+        source_hash: String,
+        config_hash: Option<String>,
+        output_hash: String,
+    ) -> Self {
+        ManifestEntry {
+            source: source.into(),
+            source_hash,
+            config_hash,
+            version: VERSION.to_string(),
+            output_hash,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns whether the inputs of the output are unchanged and the output was not modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_hash` - The hash of the source file.
+    /// * `config_hash` - The hash of the settings the output depends on, if any.
+    /// * `output_path` - The path of the output file.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `false` if the file must be converted again.
+    pub fn is_unchanged(
+        &self,
+        source_hash: &str,
+        config_hash: Option<&str>,
+        output_path: &Path,
+    ) -> bool {
+        let inputs_match = self.source_hash == source_hash
+            && self.config_hash.as_deref() == config_hash
+            && self.version == VERSION;
+
+        // Convert the file again if the output was deleted or edited by hand
+        inputs_match
+            && fs::read(output_path).is_ok_and(|output| hash_bytes(&output) == self.output_hash)
+    }
+}
+
+/// `Manifest` lists the files zenn2press wrote in a destination directory.
+///
+/// It is stored as [`MANIFEST_FILE`] in the directory and keyed by the path of each
+/// output relative to it. A file whose source, settings and zenn2press version match
+/// its entry, and whose output was not modified since, is not converted again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The entries, keyed by the relative path of the output.
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Reads the manifest of a destination directory.
+    ///
+    /// A missing, unreadable or invalid manifest is read as empty, so that every file
+    /// is converted again.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The destination directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Manifest` - The manifest of the directory.
+    pub fn read(dir: &Path) -> Manifest {
+        fs::read_to_string(dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the manifest to a destination directory, unless it is already up to date.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The destination directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - An error if the manifest cannot be serialized or written.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(MANIFEST_FILE);
+
+        // Do not create a manifest for a directory without outputs
+        if self.files.is_empty() && !path.exists() {
+            return Ok(());
+        }
+
+        let content = serde_json::to_string_pretty(self).map_err(|e| Error::transform(&path, e))?;
+        let content = content + "\n";
+        if fs::read_to_string(&path).is_ok_and(|existing| existing == content) {
+            return Ok(());
+        }
+
+        fs::write(&path, content).with_path(&path)
+    }
+
+    /// Deletes the outputs whose source no longer exists and removes their entries.
+    ///
+    /// Only the files listed in the manifest are considered, so files that zenn2press
+    /// did not write are never deleted. An output edited since it was written is kept
+    /// and reported as skipped. Directories left empty by a deletion are removed.
+    /// In dry-run mode, the outputs are reported but not deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `src_dir` - The source directory, where the source of each output has the same relative path.
+    /// * `dest_dir` - The destination directory.
+    /// * `kind` - The kind of the outputs, for the report.
+    /// * `dry_run` - Whether to report the outputs without deleting them.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<FileReport>>` - The deleted and kept outputs, or an `Error::Io` if one cannot be deleted.
+    pub fn prune(
+        &mut self,
+        src_dir: &Path,
+        dest_dir: &Path,
+        kind: FileKind,
+        dry_run: bool,
+    ) -> Result<Vec<FileReport>> {
+        let orphans: Vec<String> = self
+            .files
+            .keys()
+            .filter(|key| !src_dir.join(key).exists())
+            .cloned()
+            .collect();
+
+        let mut reports = Vec::new();
+        for key in orphans {
+            let entry = self.files.remove(&key).unwrap();
+            let output_path = dest_dir.join(&key);
+
+            // Forget outputs that were already deleted
+            let Ok(output) = fs::read(&output_path) else {
+                continue;
+            };
+
+            let mut report = if hash_bytes(&output) == entry.output_hash {
+                if !dry_run {
+                    fs::remove_file(&output_path).with_path(&output_path)?;
+                    remove_empty_parents(&output_path, dest_dir);
+                }
+                let mut report = FileReport::new(kind, FileStatus::Deleted, &entry.source);
+                report.action = Some(Action::Delete);
+                report
+            } else {
+                FileReport::skipped(
+                    kind,
+                    &entry.source,
+                    "The source was removed, but the output was edited and is kept",
+                )
+            };
+            report.destination = Some(output_path);
+            reports.push(report);
+        }
+
+        Ok(reports)
+    }
+}
+
+/// Removes the parent directories of a deleted file that are left empty, up to the destination directory.
+fn remove_empty_parents(path: &Path, dest_dir: &Path) {
+    for dir in path.ancestors().skip(1) {
+        // `remove_dir` fails on directories that are not empty
+        if dir == dest_dir || !dir.starts_with(dest_dir) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
+
+/// Deletes the outputs of a destination directory whose source no longer exists.
+///
+/// # Arguments
+///
+/// * `src_dir` - The source directory.
+/// * `dest_dir` - The destination directory, with the manifest of its outputs.
+/// * `kind` - The kind of the outputs, for the report.
+/// * `mode` - How the outputs are written; nothing is deleted in dry-run mode.
+///
+/// # Returns
+///
+/// * `Result<Vec<FileReport>>` - The deleted and kept outputs, as listed by [`Manifest::prune`].
+pub fn prune_outputs(
+    src_dir: &str,
+    dest_dir: &str,
+    kind: FileKind,
+    mode: OutputMode,
+) -> Result<Vec<FileReport>> {
+    let dest_dir = Path::new(dest_dir);
+    let mut manifest = Manifest::read(dest_dir);
+    let reports = manifest.prune(Path::new(src_dir), dest_dir, kind, mode.dry_run)?;
+    if !mode.dry_run {
+        manifest.write(dest_dir)?;
+    }
+
+    Ok(reports)
+}
+
+/// Returns the SHA-256 hash of the given bytes as a hexadecimal string.
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Returns the key of an output in the manifest of a destination directory.
+///
+/// # Arguments
+///
+/// * `dest_dir` - The destination directory.
+/// * `output_path` - The path of the output file in the directory.
+///
+/// # Returns
+///
+/// * `String` - The relative path of the output, with `/` separators.
+pub fn manifest_key(dest_dir: &Path, output_path: &Path) -> String {
+    let relative = output_path.strip_prefix(dest_dir).unwrap_or(output_path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            hash_bytes(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_manifest_key() {
+        let dest_dir = Path::new("docs/public/images");
+        let output = dest_dir.join("sample").join("image.png");
+
+        assert_eq!(manifest_key(dest_dir, &output), "sample/image.png");
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let dir = tempdir().unwrap();
+        let output = dir.path().join("a.md");
+        fs::write(&output, "output").unwrap();
+
+        let mut manifest = Manifest::default();
+        let mut entry = ManifestEntry::new(
+            "zenn/articles/a.md",
+            hash_bytes(b"source"),
+            Some(hash_bytes(b"config")),
+            hash_bytes(b"output"),
+        );
+        entry.warnings.push("Image not found".to_string());
+        manifest.files.insert("a.md".to_string(), entry);
+        manifest.write(dir.path()).unwrap();
+
+        // The entry matches the same inputs and output only
+        let read = Manifest::read(dir.path());
+        assert_eq!(read, manifest);
+        let entry = &read.files["a.md"];
+        let source_hash = hash_bytes(b"source");
+        let config_hash = hash_bytes(b"config");
+        assert!(entry.is_unchanged(&source_hash, Some(&config_hash), &output));
+        assert!(!entry.is_unchanged(&hash_bytes(b"edited"), Some(&config_hash), &output));
+        assert!(!entry.is_unchanged(&source_hash, None, &output));
+
+        fs::write(&output, "edited by hand").unwrap();
+        assert!(!entry.is_unchanged(&source_hash, Some(&config_hash), &output));
+    }
+
+    #[test]
+    fn test_prune_outputs() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let (src, dest) = (src_dir.path(), dest_dir.path());
+        fs::create_dir_all(dest.join("sample")).unwrap();
+        fs::write(src.join("kept.png"), "kept").unwrap();
+        for (key, content) in [
+            ("kept.png", "kept"),
+            ("sample/removed.png", "removed"),
+            ("edited.png", "edited"),
+        ] {
+            fs::write(dest.join(key), content).unwrap();
+        }
+        fs::write(dest.join("handwritten.md"), "# Handwritten").unwrap();
+
+        let mut manifest = Manifest::default();
+        for (key, output) in [
+            ("kept.png", "kept"),
+            ("sample/removed.png", "removed"),
+            ("edited.png", "original"),
+            ("missing.png", "missing"),
+        ] {
+            let entry = ManifestEntry::new(
+                src.join(key),
+                hash_bytes(output.as_bytes()),
+                None,
+                hash_bytes(output.as_bytes()),
+            );
+            manifest.files.insert(key.to_string(), entry);
+        }
+        manifest.write(dest).unwrap();
+
+        // Only the unedited output of a removed source is deleted, with its empty directory
+        let reports = prune_outputs(
+            src.to_str().unwrap(),
+            dest.to_str().unwrap(),
+            FileKind::Image,
+            OutputMode::default(),
+        )
+        .unwrap();
+        let statuses: Vec<(FileStatus, &Path)> = reports
+            .iter()
+            .map(|report| (report.status, report.destination.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (FileStatus::Skipped, dest.join("edited.png").as_path()),
+                (
+                    FileStatus::Deleted,
+                    dest.join("sample/removed.png").as_path()
+                ),
+            ]
+        );
+        assert!(!dest.join("sample").exists());
+        assert!(dest.join("edited.png").exists());
+        assert!(dest.join("handwritten.md").exists());
+        assert!(dest.join("kept.png").exists());
+
+        let keys: Vec<String> = Manifest::read(dest).files.into_keys().collect();
+        assert_eq!(keys, vec!["kept.png"]);
+    }
+
+    #[test]
+    fn test_manifest_read_invalid() {
+        let dir = tempdir().unwrap();
+        assert_eq!(Manifest::read(dir.path()), Manifest::default());
+
+        // An empty manifest is not written to a directory without one
+        Manifest::default().write(dir.path()).unwrap();
+        assert!(!dir.path().join(MANIFEST_FILE).exists());
+
+        fs::write(dir.path().join(MANIFEST_FILE), "{ invalid").unwrap();
+        assert_eq!(Manifest::read(dir.path()), Manifest::default());
+    }
+}
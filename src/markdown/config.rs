@@ -1,21 +1,188 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
-use std::fs;
-
-/// `ConfigFile` is a structure representing a configuration file
-/// that can contain arbitrary key-value pairs.
-/// The `other` field holds a map of configuration options, where keys are strings
-/// and values are of type `serde_yaml::Value`.
-#[derive(Serialize, Deserialize)]
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use super::{
+    mapping::{ComputedValue, KeyMapping},
+    publication::PublicationOptions,
+    transform::TransformOptions,
+    validation::ValidationOptions,
+};
+use crate::error::{Error, IoResultExt, Result};
+use crate::sidebar::article::SidebarOptions;
+
+/// `ConfigFile` is a structure representing the zenn2press configuration file.
+///
+/// The file is split into sections: `frontmatter` holds the default frontmatter
+/// added to each article, `transforms` the options for converting the article body,
+/// `paths` the directories and URLs, `filters` the options for choosing which
+/// articles are emitted, `validation` the policies of the validation rules and
+/// `output` the options for generated files.
+/// Unknown keys are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ConfigFile {
-    /// A map to hold arbitrary key-value pairs from the configuration file.
-    #[serde(flatten)]
-    pub other: std::collections::HashMap<String, serde_yaml::Value>,
+    /// Frontmatter keys and values merged into each article.
+    pub frontmatter: FrontmatterConfig,
+
+    /// Options for converting Zenn-specific Markdown syntax in the article body.
+    pub transforms: TransformOptions,
+
+    /// The source and destination directories and the site URLs.
+    pub paths: PathsConfig,
+
+    /// Options for choosing which articles are emitted.
+    pub filters: FiltersConfig,
+
+    /// The policies of the rules articles are validated against.
+    pub validation: ValidationOptions,
+
+    /// Options for the files generated besides the articles.
+    pub output: OutputConfig,
+}
+
+/// `FrontmatterConfig` holds the frontmatter merged into each article, grouped by merge strategy.
+///
+/// The article's own keys are first rewritten by the `mappings` rules and the
+/// `computed` keys are inserted. The strategies are then applied in the order
+/// `defaults`, `deep_merge`, `overrides`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrontmatterConfig {
+    /// Keys applied only when the article does not set them.
+    pub defaults: IndexMap<String, serde_yaml::Value>,
+
+    /// Keys that always replace the article's value.
+    pub overrides: IndexMap<String, serde_yaml::Value>,
+
+    /// Keys merged with the article's value: mappings are merged recursively and
+    /// sequences are extended with missing items, while the article's scalars win.
+    pub deep_merge: IndexMap<String, serde_yaml::Value>,
+
+    /// Rules that rename, copy, drop or convert the article's keys, applied in order.
+    pub mappings: Vec<KeyMapping>,
+
+    /// Values computed from the article, keyed by the frontmatter key they are inserted as.
+    pub computed: IndexMap<String, ComputedValue>,
+
+    /// The reading speed used for `reading_time`, in words (or CJK characters) per minute.
+    pub reading_speed: Option<u32>,
+
+    /// The order of the keys added by the configuration. They are written after the
+    /// article's own keys, in this order first and then in the order they are configured.
+    pub order: Vec<String>,
+
+    /// Whether the comments of the article's frontmatter are written back.
+    pub preserve_comments: bool,
+}
+
+/// `PathsConfig` holds the directories and URLs used for the conversion.
+///
+/// The directories are used by the CLI when the corresponding argument is omitted.
+/// When read with [`read_config_file`], relative directories are resolved against
+/// the directory of the configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    /// Path of the root directory of Zenn content.
+    pub src_dir: Option<String>,
+
+    /// The VitePress directory where the articles are placed.
+    pub dest_dir: Option<String>,
+
+    /// The VitePress directory where the images are placed.
+    pub dest_images_dir: Option<String>,
+
+    /// The VitePress directory where the books are placed.
+    pub dest_books_dir: Option<String>,
+
+    /// The site base path (VitePress `base`) prepended to rewritten image URLs.
+    pub base: Option<String>,
+
+    /// The public URL prefix for images. Derived from the destination
+    /// images directory when not set.
+    pub image_prefix: Option<String>,
+}
+
+impl PathsConfig {
+    /// Resolves the relative directories against a base directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The directory the relative directories are relative to.
+    pub fn resolve(&mut self, base: &Path) {
+        for dir in [
+            &mut self.src_dir,
+            &mut self.dest_dir,
+            &mut self.dest_images_dir,
+            &mut self.dest_books_dir,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *dir = base.join(&*dir).to_string_lossy().to_string();
+        }
+    }
+}
+
+/// `FiltersConfig` holds the options for choosing which articles and books are emitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FiltersConfig {
+    /// File names to include. Used by the CLI when `--include` is omitted.
+    pub include: Vec<String>,
+
+    /// File names to exclude. Used by the CLI when `--exclude` is omitted.
+    pub exclude: Vec<String>,
+
+    /// Options for choosing articles based on their publication state.
+    pub publication: PublicationOptions,
+
+    /// The slugs of the books to include and exclude.
+    pub books: BookFiltersConfig,
+}
+
+/// `BookFiltersConfig` holds the options for choosing which books are emitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BookFiltersConfig {
+    /// Book slugs to include. Used by the CLI when `--include-book` is omitted.
+    pub include: Vec<String>,
+
+    /// Book slugs to exclude. Used by the CLI when `--exclude-book` is omitted.
+    pub exclude: Vec<String>,
+}
+
+/// `OutputConfig` holds the options for the files generated besides the articles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Options for generating a VitePress sidebar file from the converted articles.
+    pub sidebar: Option<SidebarOptions>,
+}
+
+impl OutputConfig {
+    /// Resolves the relative paths of the generated files against a base directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The directory the relative paths are relative to.
+    pub fn resolve(&mut self, base: &Path) {
+        if let Some(sidebar) = &mut self.sidebar {
+            sidebar.path = base.join(&sidebar.path).to_string_lossy().to_string();
+        }
+    }
 }
 
-/// Reads a YAML configuration file from the specified path and returns
+/// Reads a configuration file from the specified path and returns
 /// a `ConfigFile` struct containing the parsed configuration data.
 ///
+/// The format is chosen from the file extension: `.json`, `.toml`, or YAML otherwise.
+/// The relative directories of the `paths` section and the relative paths of the `output`
+/// section are resolved against the directory of the configuration file.
+///
 /// # Arguments
 ///
 /// * `config_file` - A string slice that holds the path to the configuration file.
@@ -23,21 +190,133 @@ pub struct ConfigFile {
 /// # Returns
 ///
 /// * `Ok(ConfigFile)` if the file is read and parsed successfully.
-/// * `Err(Box<dyn Error>)` if there is an error reading the file or parsing the YAML.
-pub fn read_config_file(config_file: &str) -> Result<ConfigFile, Box<dyn Error>> {
+/// * `Err(Error::Io)` if the file cannot be read.
+/// * `Err(Error::Config)` with the line of the error if the file cannot be parsed or contains unknown keys.
+pub fn read_config_file(config_file: &str) -> Result<ConfigFile> {
     // Read the contents of the configuration file into a string
-    let config_content = fs::read_to_string(config_file)?;
+    let config_content = fs::read_to_string(config_file).with_path(config_file)?;
+
+    // Parse the string into a ConfigFile struct according to the file extension
+    let extension = Path::new(config_file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    let config: std::result::Result<ConfigFile, (Option<usize>, String)> =
+        match extension.as_deref() {
+            Some("json") => serde_json::from_str(&config_content)
+                .map_err(|e| ((e.line() > 0).then_some(e.line()), e.to_string())),
+            Some("toml") => toml::from_str(&config_content).map_err(|e| {
+                let line = e
+                    .span()
+                    .map(|span| config_content[..span.start].matches('\n').count() + 1);
+                (line, e.message().to_string())
+            }),
+            _ => serde_yaml::from_str(&config_content)
+                .map_err(|e| (e.location().map(|l| l.line()), e.to_string())),
+        };
+
+    // Point to the offending file and line in the error
+    let mut config = config.map_err(|(line, message)| Error::Config {
+        path: config_file.into(),
+        line,
+        message,
+    })?;
 
-    // Parse the YAML string into a ConfigFile struct
-    let config: ConfigFile = serde_yaml::from_str(&config_content)?;
+    // Resolve the directories and output paths against the directory of the configuration file
+    if let Some(config_dir) = Path::new(config_file).parent() {
+        config.paths.resolve(config_dir);
+        config.output.resolve(config_dir);
+    }
 
-    // Return the parsed ConfigFile struct
     Ok(config)
 }
 
+/// Writes a starter configuration file, e.g. for `zenn2press init`.
+///
+/// The file holds the given paths and filters, and an empty `frontmatter.defaults`
+/// section to fill in. The format is chosen from the file extension, as in
+/// [`read_config_file`]: `.json`, `.toml`, or YAML otherwise.
+///
+/// # Arguments
+///
+/// * `config_file` - The path of the configuration file to write.
+/// * `paths` - The directories and URLs written in the `paths` section.
+/// * `filters` - The include and exclude lists written in the `filters` section.
+/// * `overwrite` - Whether to replace the file if it already exists.
+///
+/// # Returns
+///
+/// * `Ok(())` if the file is written.
+/// * `Err(Error::Io)` if the file exists and `overwrite` is not set, or it cannot be written.
+pub fn write_config_template(
+    config_file: &str,
+    paths: &PathsConfig,
+    filters: &FiltersConfig,
+    overwrite: bool,
+) -> Result<()> {
+    #[derive(Serialize)]
+    struct Template<'a> {
+        frontmatter: TemplateFrontmatter,
+        paths: &'a PathsConfig,
+        filters: TemplateFilters<'a>,
+    }
+
+    #[derive(Serialize)]
+    struct TemplateFrontmatter {
+        defaults: IndexMap<String, serde_yaml::Value>,
+    }
+
+    #[derive(Serialize)]
+    struct TemplateFilters<'a> {
+        include: &'a [String],
+        exclude: &'a [String],
+        books: &'a BookFiltersConfig,
+    }
+
+    let template = Template {
+        frontmatter: TemplateFrontmatter {
+            defaults: IndexMap::new(),
+        },
+        paths,
+        filters: TemplateFilters {
+            include: &filters.include,
+            exclude: &filters.exclude,
+            books: &filters.books,
+        },
+    };
+
+    // Serialize the template according to the file extension
+    let extension = Path::new(config_file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    let content = match extension.as_deref() {
+        Some("json") => serde_json::to_string_pretty(&template)
+            .map(|json| json + "\n")
+            .map_err(|e| Error::config(config_file, e))?,
+        Some("toml") => {
+            toml::to_string_pretty(&template).map_err(|e| Error::config(config_file, e))?
+        }
+        _ => serde_yaml::to_string(&template).map_err(|e| Error::config(config_file, e))?,
+    };
+
+    // Refuse to replace an existing file, unless asked to
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .create_new(!overwrite)
+        .open(config_file)
+        .with_path(config_file)?;
+    file.write_all(content.as_bytes()).with_path(config_file)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::markdown::{publication::PublicationPolicy, transform::AlertContainer};
+    use crate::sidebar::article::SidebarGroupBy;
+    use tempfile::tempdir;
 
     /// This test case verifies that the `read_config_file` function
     /// correctly reads and parses a YAML configuration file.
@@ -45,22 +324,182 @@ mod tests {
     fn test_read_config_file() {
         // Create a sample YAML content
         let yaml_content = r#"
-        key1: value1
-        key2: value2
+        frontmatter:
+          defaults:
+            key1: value1
+          overrides:
+            key2: value2
+        transforms:
+          alert: danger
+        paths:
+          base: /blog/
+        filters:
+          include: [article-1]
+          publication:
+            policy: published-only
+        output:
+          sidebar:
+            path: sidebar.json
+            group_by: topic
         "#;
 
         // Write the sample YAML content to a temporary file
-        let temp_file_path = "test_config.yaml";
-        fs::write(temp_file_path, yaml_content).expect("Unable to write test file");
+        let temp_dir = tempdir().unwrap();
+        let temp_file_path = temp_dir.path().join("config.yaml");
+        fs::write(&temp_file_path, yaml_content).expect("Unable to write test file");
 
         // Read and parse the configuration file
-        let config = read_config_file(temp_file_path).expect("Failed to read config file");
+        let config =
+            read_config_file(temp_file_path.to_str().unwrap()).expect("Failed to read config file");
 
         // Check if the parsed values match the expected values
-        assert_eq!(config.other.get("key1").unwrap(), "value1");
-        assert_eq!(config.other.get("key2").unwrap(), "value2");
+        assert_eq!(config.frontmatter.defaults.get("key1").unwrap(), "value1");
+        assert_eq!(config.frontmatter.overrides.get("key2").unwrap(), "value2");
+        assert_eq!(config.transforms.alert, AlertContainer::Danger);
+        assert_eq!(config.paths.base.as_deref(), Some("/blog/"));
+        assert_eq!(config.filters.include, vec!["article-1"]);
+        assert_eq!(
+            config.filters.publication.policy,
+            PublicationPolicy::PublishedOnly
+        );
+        assert_eq!(
+            config.output.sidebar.map(|sidebar| sidebar.group_by),
+            Some(SidebarGroupBy::Topic)
+        );
+    }
+
+    /// This test case verifies that JSON and TOML configuration files are accepted.
+    #[test]
+    fn test_read_config_file_with_json_and_toml() {
+        let temp_dir = tempdir().unwrap();
+
+        let json_path = temp_dir.path().join("config.json");
+        fs::write(
+            &json_path,
+            r#"{ "frontmatter": { "defaults": { "layout": "doc", "outline": [1, 2] } } }"#,
+        )
+        .unwrap();
+        let config = read_config_file(json_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.frontmatter.defaults.get("layout").unwrap(), "doc");
+
+        let toml_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &toml_path,
+            "[frontmatter.defaults]\nlayout = \"doc\"\n\n[paths]\nimage_prefix = \"/assets\"\n",
+        )
+        .unwrap();
+        let config = read_config_file(toml_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.frontmatter.defaults.get("layout").unwrap(), "doc");
+        assert_eq!(config.paths.image_prefix.as_deref(), Some("/assets"));
+    }
+
+    /// This test case verifies that unknown keys are rejected with a helpful error.
+    #[test]
+    fn test_read_config_file_with_unknown_keys() {
+        let temp_dir = tempdir().unwrap();
+
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, r#"{ "layout": "doc" }"#).unwrap();
+        let error = read_config_file(config_path.to_str().unwrap())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("config.json"));
+        assert!(error.contains("unknown field `layout`"));
+        assert!(error.contains("frontmatter"));
+
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(&config_path, "transforms:\n  alerts: danger\n").unwrap();
+        let error = read_config_file(config_path.to_str().unwrap())
+            .err()
+            .unwrap();
+        assert_eq!(error.line(), Some(2));
+        assert!(error.to_string().contains("unknown field `alerts`"));
+
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(&config_path, "[paths]\nbase = \"/\"\nroot = \"/\"\n").unwrap();
+        let error = read_config_file(config_path.to_str().unwrap())
+            .err()
+            .unwrap();
+        assert_eq!(error.line(), Some(3));
+        assert!(matches!(error, Error::Config { .. }));
+    }
+
+    /// This test case verifies that the relative directories of the `paths` section and the
+    /// sidebar path are resolved against the directory of the configuration file.
+    #[test]
+    fn test_read_config_file_resolves_paths() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sub");
+        fs::create_dir_all(&config_dir).unwrap();
+        let config_file = config_dir.join("zenn2press.json");
+        fs::write(
+            &config_file,
+            r#"{
+                "paths": { "src_dir": "zenn", "dest_dir": "/srv/docs/articles", "base": "/blog/" },
+                "output": { "sidebar": { "path": "docs/.vitepress/sidebar.json" } }
+            }"#,
+        )
+        .unwrap();
+
+        // Relative directories are relative to the configuration file, not the working directory
+        let config = read_config_file(config_file.to_str().unwrap()).unwrap();
+        assert_eq!(
+            config.paths.src_dir,
+            Some(config_dir.join("zenn").to_string_lossy().to_string())
+        );
+        assert_eq!(config.paths.dest_dir.as_deref(), Some("/srv/docs/articles"));
+        assert_eq!(config.paths.dest_images_dir, None);
+        assert_eq!(config.paths.base.as_deref(), Some("/blog/"));
+        assert_eq!(
+            config.output.sidebar.map(|sidebar| sidebar.path),
+            Some(
+                config_dir
+                    .join("docs/.vitepress/sidebar.json")
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+    }
+
+    /// This test case verifies that the configuration template is read back in every format,
+    /// and that an existing file is only replaced when asked to.
+    #[test]
+    fn test_write_config_template() {
+        let temp_dir = tempdir().unwrap();
+        let paths = PathsConfig {
+            src_dir: Some(".".into()),
+            dest_dir: Some("docs/articles".into()),
+            dest_images_dir: Some("docs/public/images".into()),
+            ..Default::default()
+        };
+        let filters = FiltersConfig {
+            exclude: vec!["draft".into()],
+            ..Default::default()
+        };
+
+        // The directories are read back relative to the configuration file
+        let mut resolved = paths.clone();
+        resolved.resolve(temp_dir.path());
+        for name in ["zenn2press.json", "zenn2press.yaml", "zenn2press.toml"] {
+            let path = temp_dir.path().join(name);
+            let path = path.to_str().unwrap();
+            write_config_template(path, &paths, &filters, false).unwrap();
+
+            let config = read_config_file(path).unwrap();
+            assert_eq!(config.paths, resolved);
+            assert_eq!(config.filters, filters);
+            assert!(config.frontmatter.defaults.is_empty());
+        }
 
-        // Clean up the temporary file
-        fs::remove_file(temp_file_path).expect("Unable to delete test file");
+        let path = temp_dir.path().join("zenn2press.json");
+        let path = path.to_str().unwrap();
+        let error = write_config_template(path, &paths, &filters, false).unwrap_err();
+        assert!(matches!(error, Error::Io { .. }));
+        write_config_template(path, &PathsConfig::default(), &filters, true).unwrap();
+        assert_eq!(
+            read_config_file(path).unwrap().paths,
+            PathsConfig::default()
+        );
     }
 }
@@ -1,67 +1,259 @@
-use super::{config::ConfigFile, frontmatter::parse_frontmatter};
+use super::{
+    config::{ConfigFile, FrontmatterConfig},
+    frontmatter::{parse_frontmatter, parse_frontmatter_comments, render_frontmatter, Frontmatter},
+    mapping::{apply_mappings, insert_computed_keys},
+    transform::{
+        heading::insert_title_heading, link::ImageLinks, transform_body, TransformOptions,
+    },
+};
 
-use std::{fs, path::Path};
+use serde_yaml::Value;
+
+use crate::error::{IoResultExt, Result};
+use crate::output::{write_output, OutputMode};
+use crate::report::{Action, FileKind, FileReport, FileStatus};
+use std::{fs, path::Path, time::Instant};
 
 /// Copies a Markdown file to a destination directory, optionally updating its frontmatter.
 ///
-/// This function reads the content of the specified Markdown file, parses its frontmatter,
-/// and optionally merges it with additional configuration data. If the frontmatter does not
-/// contain a "title" key, the function will log a message and exit early. Otherwise, it updates
-/// the content of the file by adding the title at the beginning and writes the updated file
-/// to the destination directory.
+/// This function converts the specified Markdown file with [`convert_markdown_file`] and
+/// writes the result to the destination directory. The destination is not written when it
+/// already has the converted content or in dry-run mode. The warnings raised by the conversion are
+/// reported.
 ///
 /// # Arguments
 ///
 /// * `dest_dir` - The destination directory where the file will be copied.
 /// * `file` - The path to the Markdown file that needs to be copied.
 /// * `frontmatter_config` - Optional configuration data to merge with the file's frontmatter.
+/// * `image_links` - Optional settings for rewriting `/images/...` URLs in the body.
+/// * `mode` - How the destination is written.
 ///
 /// # Returns
 ///
-/// * `Result<(), Box<dyn std::error::Error>>` - Returns `Ok(())` on success, or an error wrapped in `Box` on failure.
+/// * `Result<FileReport>` - The report entry of the file, or an [`Error`](crate::Error) naming the offending file on failure.
 pub fn copy_markdown_file(
     dest_dir: &str,
     file: &Path,
     frontmatter_config: Option<&ConfigFile>,
-) -> Result<(), Box<dyn std::error::Error>> {
+    image_links: Option<&ImageLinks>,
+    mode: OutputMode,
+) -> Result<FileReport> {
+    let started = Instant::now();
+
+    // Convert the file, collecting its warnings
+    let mut warnings = Vec::new();
+    let new_file_content =
+        convert_markdown_file(file, frontmatter_config, image_links, &mut warnings)?;
+
+    // Construct the full path of the file in the destination directory
+    let output_file_path = Path::new(dest_dir).join(file.file_name().unwrap());
+
+    // Write the new content into the file in the destination directory, unless it is up to date
+    let written = write_output(&output_file_path, &new_file_content, mode)?;
+    let status = match written.action {
+        Action::Unchanged => FileStatus::Unchanged,
+        _ => FileStatus::Converted,
+    };
+
+    let mut report = FileReport::new(FileKind::Article, status, file);
+    report.destination = Some(output_file_path);
+    report.action = Some(written.action);
+    report.diff = written.diff;
+    report.warnings = warnings;
+    Ok(report.timed(started))
+}
+
+/// Converts a Zenn Markdown file into VitePress Markdown.
+///
+/// This function reads the content of the specified Markdown file, parses its frontmatter,
+/// and optionally merges it with additional configuration data. It then converts
+/// Zenn-specific syntax in the body to VitePress syntax and adds the title at the beginning.
+/// Articles missing a title are left to the validation rules.
+///
+/// # Arguments
+///
+/// * `file` - The path to the Markdown file that needs to be converted.
+/// * `frontmatter_config` - Optional configuration data to merge with the file's frontmatter.
+/// * `image_links` - Optional settings for rewriting `/images/...` URLs in the body.
+/// * `warnings` - A list where the warnings raised by the conversion are added.
+///
+/// # Returns
+///
+/// * `Result<String>` - The converted file content. Errors name the converted file.
+pub fn convert_markdown_file(
+    file: &Path,
+    frontmatter_config: Option<&ConfigFile>,
+    image_links: Option<&ImageLinks>,
+    warnings: &mut Vec<String>,
+) -> Result<String> {
     // Read the content of the file
-    let file_content = fs::read_to_string(&file)?;
+    let file_content = fs::read_to_string(file).with_path(file)?;
 
     // Parse the frontmatter of the file
-    let (content, data) = parse_frontmatter(&file_content)?;
+    let (content, data) = parse_frontmatter(&file_content).map_err(|e| e.with_path(file))?;
+
+    // Map the article's keys, then merge frontmatter data with the provided config, if any
+    let merged_data = merge_article_frontmatter(file, &content, data, frontmatter_config, warnings);
+
+    // Convert Zenn-specific syntax in the body using the configured transforms
+    let default_transforms = TransformOptions::default();
+    let transforms = frontmatter_config
+        .map(|config| &config.transforms)
+        .unwrap_or(&default_transforms);
+    let transformed = transform_body(&content, transforms, image_links);
+    warnings.extend(transformed.warnings);
+    let content = transformed.body;
+
+    // Update the content of the file by adding the title heading, if configured
+    let updated_content = insert_title_heading(&content, &merged_data, transforms, warnings);
+
+    // Stringify the updated content and the frontmatter data, with its comments if configured
+    let comments = frontmatter_config
+        .filter(|config| config.frontmatter.preserve_comments)
+        .map(|_| parse_frontmatter_comments(&file_content));
+    let new_file_content = format!(
+        "---\n{}---\n{}",
+        render_frontmatter(&merged_data, comments.as_ref()).map_err(|e| e.with_path(file))?,
+        updated_content
+    );
+
+    Ok(new_file_content)
+}
 
-    // Merge frontmatter data with the provided config, if any
+/// Returns the frontmatter written for an article: its own keys rewritten by the
+/// `mappings` rules, with the `computed` keys and the configured frontmatter merged in.
+///
+/// # Arguments
+///
+/// * `file` - The path of the article, used to derive its slug.
+/// * `body` - The body of the article, used by the computed keys.
+/// * `data` - The frontmatter of the article.
+/// * `frontmatter_config` - Optional configuration data to merge with the frontmatter.
+/// * `warnings` - A list where the warnings raised by the mappings are added.
+///
+/// # Returns
+///
+/// * `Frontmatter` - The merged frontmatter, in the order it is written.
+pub fn merge_article_frontmatter(
+    file: &Path,
+    body: &str,
+    data: Frontmatter,
+    frontmatter_config: Option<&ConfigFile>,
+    warnings: &mut Vec<String>,
+) -> Frontmatter {
     let mut merged_data = data;
     if let Some(config) = frontmatter_config {
-        merged_data.extend(config.other.clone());
+        apply_mappings(&mut merged_data, &config.frontmatter.mappings, warnings);
+        let authored_len = merged_data.len();
+        insert_computed_keys(
+            &mut merged_data,
+            &config.frontmatter.computed,
+            &file_slug(file),
+            body,
+            config.frontmatter.reading_speed,
+        );
+        merge_frontmatter(&mut merged_data, &config.frontmatter);
+        order_injected_keys(&mut merged_data, authored_len, &config.frontmatter.order);
     }
+    merged_data
+}
 
-    // Check if the file has a title in its frontmatter
-    if !merged_data.contains_key("title") {
-        println!("No title found in the frontmatter of {:?}", file);
-        return Ok(());
+/// Derives the slug of an article or chapter from its file name.
+///
+/// The numeric prefix of book chapters named `<number>.<slug>.md` is removed.
+fn file_slug(file: &Path) -> String {
+    let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+    match stem.split_once('.') {
+        Some((number, slug)) if number.chars().all(|c| c.is_ascii_digit()) => slug.to_string(),
+        _ => stem.to_string(),
     }
+}
 
-    // Convert the title to a string
-    let title = merged_data["title"].as_str().unwrap_or("Untitled");
+/// Merges the configured frontmatter into the frontmatter of an article.
+///
+/// `defaults` are applied only to keys the article does not set, `deep_merge` values
+/// are merged with [`deep_merge_value`], and `overrides` always replace the article's value.
+///
+/// # Arguments
+///
+/// * `data` - The frontmatter of the article, updated in place.
+/// * `config` - The frontmatter section of the configuration file.
+pub fn merge_frontmatter(data: &mut Frontmatter, config: &FrontmatterConfig) {
+    // Apply the defaults to the keys the author left out
+    for (key, value) in &config.defaults {
+        data.entry(key.clone()).or_insert_with(|| value.clone());
+    }
 
-    // Update the content of the file by adding the title at the beginning
-    let updated_content = format!("# {}\n{}", title, content);
+    // Merge nested mappings and sequences with the author's values
+    for (key, value) in &config.deep_merge {
+        match data.get_mut(key) {
+            Some(existing) => deep_merge_value(existing, value),
+            None => {
+                data.insert(key.clone(), value.clone());
+            }
+        }
+    }
 
-    // Stringify the updated content and the frontmatter data
-    let new_file_content = format!(
-        "---\n{}---\n{}",
-        serde_yaml::to_string(&merged_data)?,
-        updated_content
-    );
+    // Apply the overrides last so that they always win
+    for (key, value) in &config.overrides {
+        data.insert(key.clone(), value.clone());
+    }
+}
 
-    // Construct the full path of the file in the destination directory
-    let output_file_path = Path::new(dest_dir).join(file.file_name().unwrap());
+/// Sorts the keys added after the article's own keys by the configured order.
+///
+/// Keys listed in `order` come first, in that order, followed by the other added keys
+/// in the order they were added. The article's own keys are left in place.
+///
+/// # Arguments
+///
+/// * `data` - The frontmatter of the article, updated in place.
+/// * `authored_len` - The number of keys written by the author, which come first in `data`.
+/// * `order` - The configured order of the added keys.
+pub fn order_injected_keys(data: &mut Frontmatter, authored_len: usize, order: &[String]) {
+    if order.is_empty() || authored_len >= data.len() {
+        return;
+    }
 
-    // Write the new content into the file in the destination directory
-    fs::write(output_file_path, new_file_content)?;
+    // Sort the added keys by their position in the configured order, keeping ties stable
+    let mut injected = data.split_off(authored_len);
+    injected
+        .sort_by_cached_key(|key, _| order.iter().position(|o| o == key).unwrap_or(order.len()));
+    data.extend(injected);
+}
 
-    Ok(())
+/// Merges a configured value into an existing frontmatter value.
+///
+/// Mappings are merged key by key, recursively. Sequences are extended with the
+/// configured items they do not already contain. For any other combination the
+/// existing value is kept.
+///
+/// # Arguments
+///
+/// * `existing` - The value written by the author, updated in place.
+/// * `value` - The configured value.
+pub fn deep_merge_value(existing: &mut Value, value: &Value) {
+    match (existing, value) {
+        (Value::Mapping(existing), Value::Mapping(value)) => {
+            for (key, value) in value {
+                match existing.get_mut(key) {
+                    Some(existing) => deep_merge_value(existing, value),
+                    None => {
+                        existing.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (Value::Sequence(existing), Value::Sequence(value)) => {
+            for item in value {
+                if !existing.contains(item) {
+                    existing.push(item.clone());
+                }
+            }
+        }
+        _ => {}
+    }
 }
 
 #[cfg(test)]
@@ -92,10 +284,18 @@ This is a test markdown file.
         let dest_dir = tempdir().unwrap();
 
         // Call the function to copy the markdown file
-        let result = copy_markdown_file(dest_dir.path().to_str().unwrap(), &temp_file_path, None);
+        let result = copy_markdown_file(
+            dest_dir.path().to_str().unwrap(),
+            &temp_file_path,
+            None,
+            None,
+            OutputMode::default(),
+        );
 
         // Check if the operation was successful
-        assert!(result.is_ok());
+        let report = result.unwrap();
+        assert_eq!(report.status, FileStatus::Converted);
+        assert_eq!(report.destination, Some(dest_dir.path().join("test.md")));
 
         // Check if the file was created in the destination directory
         let copied_file_path = dest_dir.path().join("test.md");
@@ -105,5 +305,207 @@ This is a test markdown file.
         let copied_content = fs::read_to_string(copied_file_path).unwrap();
         assert!(copied_content.contains("# Test Title"));
         assert!(copied_content.contains("This is a test markdown file."));
+
+        // A second copy leaves the up-to-date file alone
+        let report = copy_markdown_file(
+            dest_dir.path().to_str().unwrap(),
+            &temp_file_path,
+            None,
+            None,
+            OutputMode::default(),
+        )
+        .unwrap();
+        assert_eq!(report.status, FileStatus::Unchanged);
+    }
+
+    #[test]
+    fn test_copy_markdown_file_converts_body() {
+        let temp_dir = tempdir().unwrap();
+        let temp_file_path = temp_dir.path().join("test.md");
+
+        // Sample content with a Zenn message block
+        let content = "---\ntitle: Test\n---\n:::message alert\nCaution\n:::\n";
+        fs::write(&temp_file_path, content).unwrap();
+
+        let dest_dir = tempdir().unwrap();
+        copy_markdown_file(
+            dest_dir.path().to_str().unwrap(),
+            &temp_file_path,
+            None,
+            None,
+            OutputMode::default(),
+        )
+        .unwrap();
+
+        // The message block is converted into a VitePress container
+        let copied_content = fs::read_to_string(dest_dir.path().join("test.md")).unwrap();
+        assert!(copied_content.contains("::: warning\nCaution\n:::"));
+        assert!(!copied_content.contains(":::message"));
+    }
+
+    #[test]
+    fn test_copy_markdown_file_rewrites_image_links() {
+        let temp_dir = tempdir().unwrap();
+        let temp_file_path = temp_dir.path().join("test.md");
+        let content = "---\ntitle: Test\n---\n![Image](/images/test/a.png)\n";
+        fs::write(&temp_file_path, content).unwrap();
+
+        let image_links = ImageLinks {
+            prefix: "/assets/zenn".to_string(),
+            src_images_dir: temp_dir.path().join("images"),
+        };
+
+        let dest_dir = tempdir().unwrap();
+        copy_markdown_file(
+            dest_dir.path().to_str().unwrap(),
+            &temp_file_path,
+            None,
+            Some(&image_links),
+            OutputMode::default(),
+        )
+        .unwrap();
+
+        // The image URL points to the destination images directory
+        let copied_content = fs::read_to_string(dest_dir.path().join("test.md")).unwrap();
+        assert!(copied_content.contains("![Image](/assets/zenn/test/a.png)"));
+    }
+
+    /// Builds a frontmatter map from YAML for the merge tests.
+    fn yaml_map<T: serde::de::DeserializeOwned>(yaml: &str) -> T {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_merge_frontmatter_defaults_and_overrides() {
+        let mut data = yaml_map("title: Article\nlayout: home\n");
+        let config = FrontmatterConfig {
+            defaults: yaml_map("title: Default\nlayout: doc\naside: true\n"),
+            overrides: yaml_map("editLink: false\nlayout: page\n"),
+            ..Default::default()
+        };
+
+        merge_frontmatter(&mut data, &config);
+
+        // Defaults do not replace the author's values, overrides always do
+        assert_eq!(data["title"], "Article");
+        assert_eq!(data["aside"], true);
+        assert_eq!(data["editLink"], false);
+        assert_eq!(data["layout"], "page");
+    }
+
+    #[test]
+    fn test_merge_frontmatter_deep_merge() {
+        let mut data = yaml_map(
+            "head:\n  - [meta, {name: author, content: me}]\noutline: [2]\nprev:\n  text: Back\n",
+        );
+        let config = FrontmatterConfig {
+            deep_merge: yaml_map(
+                "head:\n  - [meta, {name: author, content: me}]\n  - [link, {rel: icon, href: /favicon.ico}]\noutline: [2, 3]\nprev:\n  text: Previous\n  link: /\nnext: false\n",
+            ),
+            ..Default::default()
+        };
+
+        merge_frontmatter(&mut data, &config);
+
+        // Sequences gain the missing items, mappings gain the missing keys
+        assert_eq!(data["head"].as_sequence().unwrap().len(), 2);
+        assert_eq!(data["outline"], yaml_map::<Frontmatter>("v: [2, 3]")["v"]);
+        assert_eq!(data["prev"]["text"], "Back");
+        assert_eq!(data["prev"]["link"], "/");
+        assert_eq!(data["next"], false);
+    }
+
+    #[test]
+    fn test_copy_markdown_file_keeps_author_frontmatter() {
+        let temp_dir = tempdir().unwrap();
+        let temp_file_path = temp_dir.path().join("test.md");
+        fs::write(&temp_file_path, "---\ntitle: Author Title\n---\nBody\n").unwrap();
+
+        let mut config = ConfigFile::default();
+        config.frontmatter.defaults = yaml_map("title: Config Title\nlayout: doc\n");
+
+        let dest_dir = tempdir().unwrap();
+        copy_markdown_file(
+            dest_dir.path().to_str().unwrap(),
+            &temp_file_path,
+            Some(&config),
+            None,
+            OutputMode::default(),
+        )
+        .unwrap();
+
+        // The author's title is kept while the missing layout is filled in
+        let copied_content = fs::read_to_string(dest_dir.path().join("test.md")).unwrap();
+        assert!(copied_content.contains("title: Author Title"));
+        assert!(copied_content.contains("layout: doc"));
+        assert!(copied_content.contains("# Author Title"));
+    }
+
+    #[test]
+    fn test_copy_markdown_file_maps_frontmatter() {
+        let temp_dir = tempdir().unwrap();
+        let temp_file_path = temp_dir.path().join("1.intro.md");
+        let content = "---\ntitle: Test\nemoji: '🎉'\ntopics: [rust]\npublished_at: 2024-08-04 09:00\n---\nOne two three\n";
+        fs::write(&temp_file_path, content).unwrap();
+
+        let config: ConfigFile = serde_yaml::from_str(
+            r#"
+            frontmatter:
+              mappings:
+                - { from: emoji, to: icon }
+                - { from: topics, to: tags }
+                - { from: published_at, to: date, convert: date }
+              computed:
+                slug: slug
+                wordCount: word_count
+            "#,
+        )
+        .unwrap();
+
+        let converted =
+            convert_markdown_file(&temp_file_path, Some(&config), None, &mut Vec::new()).unwrap();
+        let (_, data) = parse_frontmatter(&converted).unwrap();
+
+        assert_eq!(data["icon"], "🎉");
+        assert_eq!(data["date"], "2024-08-04");
+        assert_eq!(data["slug"], "intro");
+        // The title heading is not part of the counted body
+        assert_eq!(data["wordCount"], 3);
+        assert!(!data.contains_key("topics"));
+        assert!(data["tags"].is_sequence());
+    }
+
+    #[test]
+    fn test_copy_markdown_file_keeps_key_order() {
+        let temp_dir = tempdir().unwrap();
+        let temp_file_path = temp_dir.path().join("test.md");
+        let content = "---\ntitle: Test\ntype: \"tech\" # tech: 技術記事 / idea: アイデア\npublished: true\n---\nBody\n";
+        fs::write(&temp_file_path, content).unwrap();
+
+        let config: ConfigFile = serde_yaml::from_str(
+            r#"
+            frontmatter:
+              defaults: { layout: doc, aside: true, title: Default }
+              overrides: { editLink: false }
+              order: [editLink]
+              preserve_comments: true
+            "#,
+        )
+        .unwrap();
+
+        // The output is the same on every run
+        let converted =
+            convert_markdown_file(&temp_file_path, Some(&config), None, &mut Vec::new()).unwrap();
+        for _ in 0..5 {
+            let again =
+                convert_markdown_file(&temp_file_path, Some(&config), None, &mut Vec::new())
+                    .unwrap();
+            assert_eq!(again, converted);
+        }
+
+        // The author's keys come first, then the added keys in the configured order
+        assert!(converted.starts_with(
+            "---\ntitle: Test\ntype: tech # tech: 技術記事 / idea: アイデア\npublished: true\neditLink: false\nlayout: doc\naside: true\n---\n"
+        ));
     }
 }
@@ -1,5 +1,14 @@
+use std::fs;
 use std::path::PathBuf;
 
+use super::{
+    frontmatter::parse_frontmatter,
+    publication::{
+        check_publication, resolve_now, PublicationOptions, PublicationPolicy, SkipReason,
+    },
+};
+use crate::error::Result;
+
 /// Filters a list of files to include only markdown files, with optional include/exclude filters.
 ///
 /// This function filters a list of `PathBuf` entries to include only those with a `.md` extension.
@@ -48,6 +57,51 @@ pub fn filter_markdown_files(
     markdown_files
 }
 
+/// Filters a list of markdown files by the publication options.
+///
+/// The frontmatter of each file is evaluated with [`check_publication`]. Files whose
+/// frontmatter cannot be read or parsed are kept, so that the error is reported when
+/// they are converted.
+///
+/// # Arguments
+///
+/// * `files` - A vector of `PathBuf` representing markdown file paths.
+/// * `options` - The publication options.
+///
+/// # Returns
+///
+/// * `Result<(Vec<PathBuf>, Vec<(PathBuf, SkipReason)>)>` - The files to emit and the
+///   skipped files with the reason they were skipped, or an `Error::Config` if `now` cannot be parsed.
+#[allow(clippy::type_complexity)]
+pub fn filter_published_files(
+    files: Vec<PathBuf>,
+    options: &PublicationOptions,
+) -> Result<(Vec<PathBuf>, Vec<(PathBuf, SkipReason)>)> {
+    // Every file is emitted with the `all` policy
+    if options.policy == PublicationPolicy::All {
+        return Ok((files, Vec::new()));
+    }
+
+    let now = resolve_now(options)?;
+    let mut emitted = Vec::new();
+    let mut skipped = Vec::new();
+
+    for file in files {
+        // Evaluate the publication policy against the frontmatter
+        let reason = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| parse_frontmatter(&content).ok())
+            .and_then(|(_, data)| check_publication(&data, options, now));
+
+        match reason {
+            Some(reason) => skipped.push((file, reason)),
+            None => emitted.push(file),
+        }
+    }
+
+    Ok((emitted, skipped))
+}
+
 /// Adds the `.md` extension to a list of file names if it is not already present.
 ///
 /// This utility function takes a list of file names (without extensions) and ensures they all
@@ -120,6 +174,46 @@ mod tests {
         assert_eq!(result, vec![PathBuf::from("file1.md")]);
     }
 
+    #[test]
+    fn test_filter_published_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let published = temp_dir.path().join("published.md");
+        let draft = temp_dir.path().join("draft.md");
+        let scheduled = temp_dir.path().join("scheduled.md");
+        fs::write(&published, "---\npublished: true\n---\n").unwrap();
+        fs::write(&draft, "---\npublished: false\n---\n").unwrap();
+        fs::write(
+            &scheduled,
+            "---\npublished: true\npublished_at: 2099-01-01 00:00\n---\n",
+        )
+        .unwrap();
+        let files = vec![published.clone(), draft.clone(), scheduled.clone()];
+
+        // Test case: all articles are emitted by default
+        let (emitted, skipped) =
+            filter_published_files(files.clone(), &PublicationOptions::default()).unwrap();
+        assert_eq!(emitted, files);
+        assert!(skipped.is_empty());
+
+        // Test case: drafts and scheduled articles are skipped
+        let options = PublicationOptions {
+            policy: PublicationPolicy::PublishedOnly,
+            now: Some("2024-08-04 09:00".to_string()),
+        };
+        let (emitted, skipped) = filter_published_files(files, &options).unwrap();
+        assert_eq!(emitted, vec![published]);
+        assert_eq!(
+            skipped,
+            vec![
+                (draft, SkipReason::Draft),
+                (
+                    scheduled,
+                    SkipReason::Scheduled("2099-01-01 00:00".to_string())
+                )
+            ]
+        );
+    }
+
     #[test]
     fn test_add_md_ext() {
         // Test case: file names without extension
@@ -1,15 +1,27 @@
 use std::collections::HashMap;
 
+use indexmap::IndexMap;
 use serde_yaml::Value;
 
-/// Type alias for the configuration parsed from the frontmatter.
-/// This is a mapping of string keys to YAML values.
-type FrontmatterConfig = HashMap<String, Value>;
+use crate::error::{Error, Result};
+
+/// Type alias for the data parsed from the frontmatter.
+/// This is a mapping of string keys to YAML values that keeps the author's key order.
+pub type Frontmatter = IndexMap<String, Value>;
+
+/// The line that opens and closes a frontmatter block.
+const DELIMITER: &str = "---";
+
+/// The byte order mark some editors write at the start of a file.
+const BOM: char = '\u{feff}';
 
 /// Parses the frontmatter and content from a given string.
 ///
-/// The input string is expected to contain a frontmatter section
-/// enclosed by "---" at the beginning and end, followed by the actual content.
+/// A frontmatter block is only recognised when the very first line is `---`,
+/// and it ends at the next line that is exactly `---`. A leading byte order mark
+/// and CRLF line endings are accepted. Without such a block, the whole input is
+/// the content and the frontmatter is empty. The content is returned exactly as
+/// written after the closing line.
 ///
 /// # Arguments
 ///
@@ -17,33 +29,221 @@ type FrontmatterConfig = HashMap<String, Value>;
 ///
 /// # Returns
 ///
-/// A `Result` containing a tuple with the parsed content as a string and the
-/// frontmatter as a `FrontmatterConfig` (i.e., a `HashMap<String, Value>`).
+/// A `Result` containing a tuple with the content as a string and the
+/// frontmatter as a [`Frontmatter`] (i.e., an `IndexMap<String, Value>`).
 /// If parsing fails, an error is returned.
 ///
 /// # Errors
 ///
-/// This function will return an error if the YAML frontmatter cannot be parsed.
-pub fn parse_frontmatter(
-    content: &str,
-) -> Result<(String, FrontmatterConfig), Box<dyn std::error::Error>> {
-    // Split the input string into three parts: the opening separator, the frontmatter, and the content
-    let mut parts = content.splitn(3, "---");
+/// This function will return an [`Error::Frontmatter`] with the line and column in the
+/// input if the YAML frontmatter cannot be parsed. Its path is empty, as the input is
+/// not read from a file; callers fill it in with [`Error::with_path`].
+pub fn parse_frontmatter(content: &str) -> Result<(String, Frontmatter)> {
+    // Ignore the byte order mark, if any
+    let content = content.strip_prefix(BOM).unwrap_or(content);
 
-    // Skip the first part (the opening separator)
-    parts.next();
+    // Locate the frontmatter block, or treat the whole input as the content
+    let Some((frontmatter, body)) = split_frontmatter(content) else {
+        return Ok((content.to_string(), Frontmatter::new()));
+    };
 
-    // The second part is the frontmatter, which is optional and needs to be trimmed
-    let frontmatter = parts.next().unwrap_or("").trim();
-
-    // The third part is the content, which is also optional and needs to be trimmed
-    let content = parts.next().unwrap_or("").trim();
+    // An empty block has no keys
+    if frontmatter.trim().is_empty() {
+        return Ok((body.to_string(), Frontmatter::new()));
+    }
 
-    // Parse the frontmatter into a HashMap using Serde YAML
-    let data: FrontmatterConfig = serde_yaml::from_str(frontmatter)?;
+    // Parse the frontmatter, pointing errors to the line in the input
+    let data: Frontmatter = serde_yaml::from_str(frontmatter).map_err(|e| {
+        let location = e.location();
+        Error::Frontmatter {
+            path: Default::default(),
+            // The frontmatter starts on the second line
+            line: location.as_ref().map(|l| l.line() + 1),
+            column: location.as_ref().map(|l| l.column()),
+            message: e.to_string(),
+        }
+    })?;
 
     // Return the content and the parsed frontmatter as a tuple
-    Ok((content.to_string(), data))
+    Ok((body.to_string(), data))
+}
+
+/// Splits the input into the frontmatter block and the content that follows it.
+///
+/// # Arguments
+///
+/// * `content` - The input without its byte order mark.
+///
+/// # Returns
+///
+/// * `Option<(&str, &str)>` - The frontmatter and the content, or `None` if the input has no frontmatter block.
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    // The first line must be the opening delimiter
+    let mut lines = content.split_inclusive('\n');
+    let opening = lines.next()?;
+    if !is_delimiter(opening) {
+        return None;
+    }
+
+    // Find the closing delimiter line
+    let start = opening.len();
+    let mut offset = start;
+    for line in lines {
+        if is_delimiter(line) {
+            return Some((&content[start..offset], &content[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// `FrontmatterComments` holds the comments of a frontmatter block, keyed by the
+/// top-level key they belong to. Comments inside nested values are not kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrontmatterComments {
+    /// Comment lines written above each key.
+    pub leading: HashMap<String, Vec<String>>,
+    /// Comments written at the end of the line of each key.
+    pub trailing: HashMap<String, String>,
+    /// Comment lines written after the last key.
+    pub footer: Vec<String>,
+}
+
+/// Collects the comments of the top-level keys in the frontmatter block of the input.
+///
+/// # Arguments
+///
+/// * `content` - A string slice that contains the frontmatter and content.
+///
+/// # Returns
+///
+/// * `FrontmatterComments` - The comments, empty if the input has no frontmatter block.
+pub fn parse_frontmatter_comments(content: &str) -> FrontmatterComments {
+    let content = content.strip_prefix(BOM).unwrap_or(content);
+    let mut comments = FrontmatterComments::default();
+    let Some((frontmatter, _)) = split_frontmatter(content) else {
+        return comments;
+    };
+
+    let mut pending = Vec::new();
+    for line in frontmatter.lines() {
+        let line = line.trim_end();
+        if line.trim_start().starts_with('#') {
+            // Keep comment lines until the key they precede
+            pending.push(line.trim_start().to_string());
+        } else if let Some(key) = top_level_key(line) {
+            // Attach the pending comment lines and the comment at the end of the line
+            if !pending.is_empty() {
+                comments
+                    .leading
+                    .insert(key.clone(), std::mem::take(&mut pending));
+            }
+            if let Some(comment) = trailing_comment(line) {
+                comments.trailing.insert(key, comment.to_string());
+            }
+        }
+    }
+    comments.footer = pending;
+
+    comments
+}
+
+/// Serializes frontmatter data into YAML, keeping its key order.
+///
+/// When comments are given, they are written back next to the keys they belong to.
+///
+/// # Arguments
+///
+/// * `data` - The frontmatter data.
+/// * `comments` - Optional comments collected with [`parse_frontmatter_comments`].
+///
+/// # Returns
+///
+/// * `Result<String>` - The YAML text, without the delimiter lines.
+pub fn render_frontmatter(
+    data: &Frontmatter,
+    comments: Option<&FrontmatterComments>,
+) -> Result<String> {
+    let Some(comments) = comments else {
+        return serde_yaml::to_string(data).map_err(|e| Error::transform("", e));
+    };
+
+    // Serialize each key on its own to place the comments around it
+    let mut output = String::new();
+    for (key, value) in data {
+        for comment in comments.leading.get(key).into_iter().flatten() {
+            output.push_str(comment);
+            output.push('\n');
+        }
+
+        let entry = serde_yaml::to_string(&Frontmatter::from([(key.clone(), value.clone())]))
+            .map_err(|e| Error::transform("", e))?;
+        let (first, rest) = entry.split_once('\n').unwrap_or((&entry, ""));
+        output.push_str(first);
+        if let Some(comment) = comments.trailing.get(key) {
+            output.push(' ');
+            output.push_str(comment);
+        }
+        output.push('\n');
+        output.push_str(rest);
+    }
+    for comment in &comments.footer {
+        output.push_str(comment);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Returns the key of a top-level `key: value` line.
+fn top_level_key(line: &str) -> Option<String> {
+    if line.starts_with(char::is_whitespace) || line.starts_with(['-', '#']) {
+        return None;
+    }
+
+    let (key, _) = line.split_once(':')?;
+    Some(key.trim().trim_matches(['"', '\'']).to_string())
+}
+
+/// Returns the comment at the end of a line, ignoring `#` inside quoted strings.
+///
+/// A quote opens a string only where a value starts (e.g. after `:` or `[`), so that
+/// an apostrophe inside a plain value (`title: It's fine # note`) is not a quote.
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut previous = ' ';
+    // The last character that is not whitespace, to tell where a value starts
+    let mut last = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (None, '"' | '\'') if matches!(last, None | Some(':' | '-' | '[' | '{' | ',')) => {
+                quote = Some(c)
+            }
+            // Skip the escaped quotes: `''` in single-quoted and `\"` in double-quoted strings
+            (Some('\''), '\'') if chars.peek().is_some_and(|(_, next)| *next == '\'') => {
+                chars.next();
+            }
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if previous.is_whitespace() => return Some(&line[index..]),
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            last = Some(c);
+        }
+        previous = c;
+    }
+
+    None
+}
+
+/// Returns whether a line, including its line ending, is a frontmatter delimiter.
+fn is_delimiter(line: &str) -> bool {
+    line.trim_end_matches(['\n', '\r']).trim_end() == DELIMITER
 }
 
 #[cfg(test)]
@@ -75,4 +275,102 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_frontmatter_without_frontmatter() {
+        let input = "# Heading\n\n---\n\nAfter the rule.\n";
+        let (content, config) = parse_frontmatter(input).unwrap();
+
+        assert_eq!(content, input);
+        assert!(config.is_empty());
+    }
+
+    #[test]
+    fn test_parse_frontmatter_preserves_body() {
+        let input = "---\ntitle: 'A --- B'\n---\n    indented\n\n---\n\nText\n\n";
+        let (content, config) = parse_frontmatter(input).unwrap();
+
+        // Dashes inside values and horizontal rules in the body are not delimiters
+        assert_eq!(config.get("title").unwrap(), "A --- B");
+        assert_eq!(content, "    indented\n\n---\n\nText\n\n");
+    }
+
+    #[test]
+    fn test_parse_frontmatter_with_crlf_and_bom() {
+        let input = "\u{feff}---\r\ntitle: Example\r\n---\r\nLine 1\r\nLine 2\r\n";
+        let (content, config) = parse_frontmatter(input).unwrap();
+
+        assert_eq!(config.get("title").unwrap(), "Example");
+        assert_eq!(content, "Line 1\r\nLine 2\r\n");
+    }
+
+    #[test]
+    fn test_parse_frontmatter_without_closing_delimiter() {
+        let input = "---\ntitle: Example\nNo closing line.\n";
+        let (content, config) = parse_frontmatter(input).unwrap();
+
+        assert_eq!(content, input);
+        assert!(config.is_empty());
+    }
+
+    #[test]
+    fn test_parse_frontmatter_reports_error_location() {
+        let input = "---\ntitle: Example\nemoji: [Invalid\n---\nThis is the content.";
+        let error = parse_frontmatter(input).unwrap_err().with_path("a.md");
+
+        assert!(matches!(
+            error,
+            Error::Frontmatter {
+                line: Some(4),
+                column: Some(_),
+                ..
+            }
+        ));
+        assert!(error
+            .to_string()
+            .starts_with("Invalid frontmatter in \"a.md\" at line 4"));
+    }
+
+    #[test]
+    fn test_parse_frontmatter_keeps_key_order() {
+        let input =
+            "---\ntitle: Example\nemoji: '🎉'\ntype: tech\ntopics: []\npublished: true\n---\n";
+        let (_, data) = parse_frontmatter(input).unwrap();
+
+        let keys: Vec<&str> = data.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["title", "emoji", "type", "topics", "published"]);
+        assert_eq!(
+            render_frontmatter(&data, None).unwrap(),
+            "title: Example\nemoji: 🎉\ntype: tech\ntopics: []\npublished: true\n"
+        );
+    }
+
+    #[test]
+    fn test_render_frontmatter_with_comments() {
+        let input = "---\n# The article\ntitle: 'A # B'\ntype: \"tech\" # tech: 技術記事 / idea: アイデア\ntopics: [rust] # up to 5\n# end\n---\nBody\n";
+        let (_, data) = parse_frontmatter(input).unwrap();
+        let comments = parse_frontmatter_comments(input);
+
+        assert_eq!(comments.leading["title"], vec!["# The article"]);
+        assert!(!comments.trailing.contains_key("title"));
+        assert_eq!(
+            render_frontmatter(&data, Some(&comments)).unwrap(),
+            "# The article\ntitle: 'A # B'\ntype: tech # tech: 技術記事 / idea: アイデア\ntopics: # up to 5\n- rust\n# end\n"
+        );
+    }
+
+    #[test]
+    fn test_trailing_comment() {
+        assert_eq!(trailing_comment("title: It's fine # note"), Some("# note"));
+        assert_eq!(trailing_comment("title: 'It''s # not a note'"), None);
+        assert_eq!(
+            trailing_comment("title: \"A \\\" # B\" # note"),
+            Some("# note")
+        );
+        assert_eq!(
+            trailing_comment("topics: ['a#b', \"c # d\"] # note"),
+            Some("# note")
+        );
+        assert_eq!(trailing_comment("url: https://example.com/#top"), None);
+    }
 }
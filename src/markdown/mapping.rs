@@ -0,0 +1,369 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use super::frontmatter::Frontmatter;
+use super::publication::parse_datetime;
+use super::transform::lines::{scan_lines, LineKind};
+
+/// The default reading speed for `reading_time`, in words (or CJK characters) per minute.
+const DEFAULT_READING_SPEED: u32 = 500;
+
+/// What a mapping rule does with its source key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingAction {
+    /// Move the value to the target key.
+    #[default]
+    Rename,
+    /// Copy the value to the target key, keeping the source key.
+    Copy,
+    /// Remove the source key.
+    Drop,
+}
+
+/// How the value of a mapping rule is converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Conversion {
+    /// Convert a scalar into a string.
+    String,
+    /// Convert a string or number into a number.
+    Number,
+    /// Convert a string or boolean into a boolean.
+    Bool,
+    /// Parse a date (e.g. Zenn's `published_at`) into an ISO date (`YYYY-MM-DD`).
+    Date,
+    /// Parse a date (e.g. Zenn's `published_at`) into an RFC 3339 timestamp.
+    Datetime,
+    /// Join a sequence into a string with the rule's separator.
+    Join,
+    /// Split a string into a sequence with the rule's separator.
+    Split,
+}
+
+/// `KeyMapping` is a rule that renames, copies, drops or converts a frontmatter key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyMapping {
+    /// The source key.
+    pub from: String,
+    /// The target key. The source key is converted in place when not set.
+    #[serde(default)]
+    pub to: Option<String>,
+    /// What the rule does with the source key.
+    #[serde(default)]
+    pub action: MappingAction,
+    /// How the value is converted, if at all.
+    #[serde(default)]
+    pub convert: Option<Conversion>,
+    /// The separator used by `join` and `split`. Defaults to `", "` and `","`.
+    #[serde(default)]
+    pub separator: Option<String>,
+}
+
+/// A value computed from the article and inserted into its frontmatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComputedValue {
+    /// The file name of the article without its extension.
+    Slug,
+    /// The number of words in the body, counting each CJK character as a word.
+    WordCount,
+    /// The estimated reading time of the body in minutes.
+    ReadingTime,
+}
+
+/// Applies the mapping rules to the frontmatter of an article, in order.
+///
+/// Rules whose source key is missing are ignored, and values that cannot be
+/// converted are left unchanged with a warning.
+///
+/// # Arguments
+///
+/// * `data` - The frontmatter of the article, updated in place.
+/// * `mappings` - The mapping rules.
+/// * `warnings` - A list that collects warnings raised during the mapping.
+pub fn apply_mappings(data: &mut Frontmatter, mappings: &[KeyMapping], warnings: &mut Vec<String>) {
+    for mapping in mappings {
+        // Drop the key, or take the value out if it is renamed, remembering its position
+        let (index, value) = match mapping.action {
+            MappingAction::Drop => {
+                data.shift_remove(&mapping.from);
+                continue;
+            }
+            MappingAction::Rename => match data.shift_remove_full(&mapping.from) {
+                Some((index, _, value)) => (Some(index), value),
+                None => continue,
+            },
+            MappingAction::Copy => match data.get(&mapping.from) {
+                Some(value) => (None, value.clone()),
+                None => continue,
+            },
+        };
+
+        // Convert the value, keeping it as is on failure
+        let value = match mapping.convert {
+            Some(conversion) => convert_value(&value, conversion, mapping.separator.as_deref())
+                .unwrap_or_else(|| {
+                    warnings.push(format!(
+                        "Frontmatter key `{}` could not be converted to {:?}",
+                        mapping.from, conversion
+                    ));
+                    value
+                }),
+            None => value,
+        };
+
+        // A renamed key takes the place of the source key, while a copy is appended
+        let to = mapping.to.clone().unwrap_or_else(|| mapping.from.clone());
+        match index {
+            Some(index) if !data.contains_key(&to) => {
+                data.shift_insert(index, to, value);
+            }
+            _ => {
+                data.insert(to, value);
+            }
+        }
+    }
+}
+
+/// Converts a frontmatter value.
+///
+/// # Arguments
+///
+/// * `value` - The value to convert.
+/// * `conversion` - The conversion to apply.
+/// * `separator` - The separator used by `join` and `split`.
+///
+/// # Returns
+///
+/// * `Option<Value>` - The converted value, or `None` if the value cannot be converted.
+pub fn convert_value(
+    value: &Value,
+    conversion: Conversion,
+    separator: Option<&str>,
+) -> Option<Value> {
+    match conversion {
+        Conversion::String => scalar_string(value).map(Value::from),
+        Conversion::Number => match value {
+            Value::Number(_) => Some(value.clone()),
+            Value::String(s) => serde_yaml::from_str::<Value>(s.trim())
+                .ok()
+                .filter(Value::is_number),
+            _ => None,
+        },
+        Conversion::Bool => match value {
+            Value::Bool(_) => Some(value.clone()),
+            Value::String(s) => s.trim().parse::<bool>().ok().map(Value::from),
+            _ => None,
+        },
+        Conversion::Date => {
+            let datetime = parse_datetime(&scalar_string(value)?)?;
+            Some(Value::from(datetime.format("%Y-%m-%d").to_string()))
+        }
+        Conversion::Datetime => {
+            let datetime = parse_datetime(&scalar_string(value)?)?;
+            Some(Value::from(datetime.to_rfc3339()))
+        }
+        Conversion::Join => {
+            let items: Option<Vec<String>> =
+                value.as_sequence()?.iter().map(scalar_string).collect();
+            Some(Value::from(items?.join(separator.unwrap_or(", "))))
+        }
+        Conversion::Split => {
+            let items = value
+                .as_str()?
+                .split(separator.unwrap_or(","))
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(Value::from)
+                .collect();
+            Some(Value::Sequence(items))
+        }
+    }
+}
+
+/// Inserts the computed keys into the frontmatter of an article.
+///
+/// # Arguments
+///
+/// * `data` - The frontmatter of the article, updated in place.
+/// * `computed` - The computed values, keyed by the frontmatter key they are inserted as.
+/// * `slug` - The slug of the article.
+/// * `body` - The body of the article.
+/// * `reading_speed` - The reading speed in words per minute. Defaults to 500.
+pub fn insert_computed_keys(
+    data: &mut Frontmatter,
+    computed: &IndexMap<String, ComputedValue>,
+    slug: &str,
+    body: &str,
+    reading_speed: Option<u32>,
+) {
+    if computed.is_empty() {
+        return;
+    }
+
+    let words = count_words(body);
+    for (key, computed_value) in computed {
+        let value = match computed_value {
+            ComputedValue::Slug => Value::from(slug),
+            ComputedValue::WordCount => Value::from(words),
+            ComputedValue::ReadingTime => {
+                let speed = reading_speed.unwrap_or(DEFAULT_READING_SPEED).max(1) as usize;
+                Value::from(words.div_ceil(speed).max(1))
+            }
+        };
+        data.insert(key.clone(), value);
+    }
+}
+
+/// Counts the words in a Markdown body, skipping fenced code blocks.
+///
+/// Each CJK character counts as one word, since Japanese text is not separated by spaces.
+pub fn count_words(body: &str) -> usize {
+    scan_lines(body)
+        .iter()
+        .filter(|line| line.kind == LineKind::Text)
+        .map(|line| {
+            let mut count = 0;
+            let mut in_word = false;
+            for c in line.content.chars() {
+                if is_cjk(c) {
+                    count += 1;
+                    in_word = false;
+                } else if c.is_alphanumeric() {
+                    count += usize::from(!in_word);
+                    in_word = true;
+                } else {
+                    in_word = in_word && !c.is_whitespace();
+                }
+            }
+            count
+        })
+        .sum()
+}
+
+/// Returns whether a character is a CJK ideograph, kana or hangul syllable.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF66}'..='\u{FF9F}')
+}
+
+/// Converts a scalar YAML value into a string.
+fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter(yaml: &str) -> Frontmatter {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn mappings(yaml: &str) -> Vec<KeyMapping> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_apply_mappings() {
+        let mut data = frontmatter(
+            "emoji: '🎉'\ntype: tech\ntopics: [rust, vitepress]\npublished_at: 2024-08-04 09:00\n",
+        );
+        let rules = mappings(
+            r#"
+            - { from: emoji, to: icon }
+            - { from: type, to: category, action: copy }
+            - { from: topics, to: tags }
+            - { from: tags, to: keywords, action: copy, convert: join }
+            - { from: published_at, to: date, convert: date }
+            - { from: type, action: drop }
+            "#,
+        );
+
+        let mut warnings = Vec::new();
+        apply_mappings(&mut data, &rules, &mut warnings);
+
+        assert!(warnings.is_empty());
+        assert_eq!(data["icon"], "🎉");
+        assert_eq!(data["category"], "tech");
+        assert_eq!(data["tags"].as_sequence().unwrap().len(), 2);
+        assert_eq!(data["keywords"], "rust, vitepress");
+        assert_eq!(data["date"], "2024-08-04");
+        for key in ["emoji", "type", "topics", "published_at"] {
+            assert!(!data.contains_key(key), "{} should be removed", key);
+        }
+
+        // Renamed keys keep their position and copies are appended
+        let keys: Vec<&str> = data.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["icon", "tags", "date", "category", "keywords"]);
+    }
+
+    #[test]
+    fn test_apply_mappings_with_invalid_value() {
+        let mut data = frontmatter("published_at: someday\n");
+        let rules = mappings("- { from: published_at, convert: datetime }");
+
+        let mut warnings = Vec::new();
+        apply_mappings(&mut data, &rules, &mut warnings);
+
+        // The value is kept as is with a warning
+        assert_eq!(data["published_at"], "someday");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("published_at"));
+    }
+
+    #[test]
+    fn test_convert_value() {
+        let value = Value::from("2024-08-04 09:00");
+        assert_eq!(
+            convert_value(&value, Conversion::Datetime, None),
+            Some(Value::from("2024-08-04T09:00:00+09:00"))
+        );
+        assert_eq!(
+            convert_value(&Value::from("42"), Conversion::Number, None),
+            Some(Value::from(42))
+        );
+        assert_eq!(
+            convert_value(&Value::from("yes"), Conversion::Bool, None),
+            None
+        );
+        assert_eq!(
+            convert_value(&Value::from(true), Conversion::String, None),
+            Some(Value::from("true"))
+        );
+        assert_eq!(
+            convert_value(&Value::from("a; b"), Conversion::Split, Some(";")),
+            Some(Value::Sequence(vec![Value::from("a"), Value::from("b")]))
+        );
+    }
+
+    #[test]
+    fn test_insert_computed_keys() {
+        let mut data = Frontmatter::new();
+        let computed = IndexMap::from([
+            ("slug".to_string(), ComputedValue::Slug),
+            ("wordCount".to_string(), ComputedValue::WordCount),
+            ("readingTime".to_string(), ComputedValue::ReadingTime),
+        ]);
+        let body = "Hello, zenn2press world!\n```rust\nlet skipped = true;\n```\nこんにちは\n";
+
+        insert_computed_keys(&mut data, &computed, "my-article", body, Some(2));
+
+        assert_eq!(data["slug"], "my-article");
+        assert_eq!(data["wordCount"], 8);
+        assert_eq!(data["readingTime"], 4);
+    }
+}
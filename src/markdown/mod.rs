@@ -1,19 +1,44 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
-use config::read_config_file;
+use config::{ConfigFile, PathsConfig};
 use copy::copy_markdown_file;
-use filter::filter_markdown_files;
+use filter::{filter_markdown_files, filter_published_files};
+use publication::SkipReason;
+use transform::link::{docs_url_path, ImageLinks};
+use validation::validate_files;
 
+use crate::converter::Converter;
+use crate::error::{Error, IoResultExt, Result};
+use crate::manifest::{hash_bytes, manifest_key, Manifest, ManifestEntry};
+use crate::output::OutputMode;
 use crate::progress_bar::get_pb;
+use crate::report::{Action, ConversionReport, FileKind, FileReport, FileStatus};
+use crate::sidebar::{article::build_article_sidebar, write_sidebar};
 
 pub mod config;
 pub mod copy;
 pub mod filter;
 pub mod frontmatter;
+pub mod mapping;
+pub mod publication;
+pub mod transform;
+pub mod validation;
 
 /// This function copies Markdown files from the source directory to the destination directory,
 /// with options for filtering and configuring frontmatter.
 ///
+/// It is a shim over [`Converter::convert_articles`], kept for compatibility. The images
+/// directories, keep-going mode and other options are only available on the builder.
+///
+/// Articles are emitted according to the `filters.publication` section of the configuration
+/// file and validated against the `validation` rules. If the configuration file has an
+/// `output.sidebar` section, a VitePress sidebar file listing the converted articles is
+/// written as well. The copy stops with an error at the first article that fails.
+///
 /// # Arguments
 ///
 /// * `src_dir` - The source directory path.
@@ -25,9 +50,10 @@ pub mod frontmatter;
 /// # Examples
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// use zenn2press::copy_markdown_files;
 ///
-/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// fn main() -> zenn2press::Result<()> {
 ///     let src_dir = "demo/zenn/articles";
 ///     let dest_dir = "demo/press/docs/articles";
 ///     let config_file = Some("demo/zenn2press-config.json");
@@ -38,41 +64,269 @@ pub mod frontmatter;
 ///     Ok(())
 /// }
 /// ```
+#[deprecated(note = "use `Converter::builder()` and `Converter::convert_articles` instead")]
 pub fn copy_markdown_files(
     src_dir: &str,
     dest_dir: &str,
     config_file: Option<&str>,
     include: Option<Vec<&str>>,
     exclude: Option<Vec<&str>>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Read all files in the source directory
-    let files: Vec<PathBuf> = fs::read_dir(src_dir)?
-        .filter_map(Result::ok)
+) -> Result<()> {
+    let mut builder = Converter::builder()
+        .src_articles_dir(src_dir)
+        .dest_dir(dest_dir)
+        .include(include.unwrap_or_default())
+        .exclude(exclude.unwrap_or_default());
+    if let Some(config_file) = config_file {
+        builder = builder.config_file(config_file);
+    }
+
+    builder.build()?.convert_articles()?.into_result()?;
+    Ok(())
+}
+
+/// Converts the articles of a converter from the source directory to the destination directory.
+///
+/// # Arguments
+///
+/// * `converter` - The converter holding the configuration, filters and images directories.
+/// * `src_dir` - The Zenn articles directory.
+/// * `dest_dir` - The VitePress directory of the articles.
+///
+/// # Returns
+///
+/// * `Result<ConversionReport>` - The outcome of every article.
+pub(crate) fn convert_articles(
+    converter: &Converter,
+    src_dir: &str,
+    dest_dir: &str,
+) -> Result<ConversionReport> {
+    let started = Instant::now();
+    let mode = converter.output_mode();
+    let mut conversion = ConversionReport {
+        dry_run: mode.dry_run,
+        ..Default::default()
+    };
+
+    // Read all files in the source directory, in a stable order for the report
+    let mut files: Vec<PathBuf> = fs::read_dir(src_dir)
+        .with_path(src_dir)?
+        .filter_map(std::result::Result::ok)
         .map(|entry| entry.path())
         .collect();
+    files.sort();
 
     // Filter the Markdown files based on the include and exclude lists
-    let markdown_files = filter_markdown_files(files, include, exclude);
+    let markdown_files = filter_markdown_files(files, converter.include(), converter.exclude());
+    let frontmatter_config = converter.config();
+    let config_file = converter.config_file.as_deref();
 
-    // Read the configuration file for frontmatter, if provided
-    let frontmatter_config = if let Some(config_file) = config_file {
-        Some(read_config_file(config_file)?)
-    } else {
-        None
-    };
+    // Filter the Markdown files based on their publication state
+    let publication = frontmatter_config
+        .map(|config| config.filters.publication.clone())
+        .unwrap_or_default();
+    let (markdown_files, mut skipped_files) = filter_published_files(markdown_files, &publication)
+        .map_err(|e| e.with_path(config_file.unwrap_or_default()))?;
+
+    // Validate the articles, leaving out those that break a `skip` or `error` rule
+    let report = validate_files(markdown_files, frontmatter_config);
+    skipped_files.extend(
+        report
+            .skipped
+            .into_iter()
+            .map(|(file, issue)| (file, SkipReason::Invalid(issue.message))),
+    );
+    let markdown_files = report.valid;
+
+    // Resolve how `/images/...` URLs are rewritten, if the images directories are provided
+    let default_paths = PathsConfig::default();
+    let paths = frontmatter_config
+        .map(|config| &config.paths)
+        .unwrap_or(&default_paths);
+    let image_links = converter
+        .images_dirs()
+        .map(|(src_images_dir, dest_images_dir)| {
+            ImageLinks::new(src_images_dir, dest_images_dir, paths)
+        });
+
+    // Create the destination directory, unless in dry-run mode
+    if !mode.dry_run {
+        fs::create_dir_all(dest_dir).with_path(dest_dir)?;
+    }
+
+    // Read the manifest of the articles converted by previous runs
+    let mut manifest = Manifest::read(Path::new(dest_dir));
+    let config_hash = config_hash(frontmatter_config, image_links.as_ref())?;
 
     let file_length = markdown_files.len().try_into().unwrap();
     let pb = get_pb(file_length);
 
-    for file in markdown_files {
+    for file in &markdown_files {
         let filename = file.file_name().unwrap().to_string_lossy().to_string();
         pb.set_message(filename);
 
         // Copy each Markdown file from the source directory to the dest directory.
-        copy_markdown_file(dest_dir, &file, frontmatter_config.as_ref())?;
+        // In keep-going mode, a failure is recorded and the next file is copied.
+        let file_started = Instant::now();
+        let converted = convert_article(
+            dest_dir,
+            file,
+            frontmatter_config,
+            image_links.as_ref(),
+            &config_hash,
+            mode,
+            &mut manifest,
+        );
+        let mut entry = match converted {
+            Ok(entry) => entry,
+            Err(e) if converter.keep_going => {
+                FileReport::failed(FileKind::Article, file, e).timed(file_started)
+            }
+            Err(e) => return Err(e),
+        };
+
+        // Report the validation warnings of the article along with its conversion warnings
+        let validation_warnings = report
+            .warnings
+            .iter()
+            .filter(|(warned, _)| warned == file)
+            .map(|(_, issue)| issue.to_string());
+        entry.warnings.splice(0..0, validation_warnings);
+        conversion.push(entry);
         pb.inc(1)
     }
     pb.finish_with_message("Completed.");
 
-    Ok(())
+    // Delete the articles whose source was removed, if requested
+    if converter.prune {
+        let pruned = manifest.prune(
+            Path::new(src_dir),
+            Path::new(dest_dir),
+            FileKind::Article,
+            mode.dry_run,
+        )?;
+        for report in pruned {
+            conversion.push(report);
+        }
+    }
+
+    // Record the converted articles for the next run
+    if !mode.dry_run {
+        manifest.write(Path::new(dest_dir))?;
+    }
+
+    // Report the articles skipped by the publication policy or validation
+    for (file, reason) in skipped_files {
+        conversion.push(FileReport::skipped(FileKind::Article, file, reason));
+    }
+
+    // Report the articles that broke an `error` rule as failed, with their issues as the message
+    for (file, issues) in report.failed {
+        let message = issues
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        let error = Error::Validation {
+            failures: vec![(file.clone(), issues)],
+        };
+        conversion.push(FileReport {
+            message: Some(message),
+            ..FileReport::failed(FileKind::Article, file, error)
+        });
+    }
+
+    // Write the sidebar file listing the converted articles, if configured
+    let sidebar = frontmatter_config.and_then(|c| c.output.sidebar.as_ref());
+    if let Some(sidebar) = sidebar.filter(|_| !mode.dry_run) {
+        let written: Vec<PathBuf> = markdown_files
+            .into_iter()
+            .filter(|file| conversion.is_written(file))
+            .collect();
+        let articles_url = docs_url_path(Path::new(dest_dir));
+        let items = build_article_sidebar(&written, &articles_url, sidebar)?;
+        write_sidebar(Path::new(&sidebar.path), &items)?;
+    }
+
+    Ok(conversion.timed(started))
+}
+
+/// Converts an article, unless the manifest records it as unchanged since the previous run.
+///
+/// The entry of the article in the manifest is updated once it is written. If the article
+/// fails to convert, the entry of the previous run is kept so that its output can still be pruned.
+///
+/// # Arguments
+///
+/// * `dest_dir` - The VitePress directory of the articles.
+/// * `file` - The Zenn article.
+/// * `frontmatter_config` - Optional configuration data to merge with the frontmatter.
+/// * `image_links` - Optional settings for rewriting `/images/...` URLs in the body.
+/// * `config_hash` - The hash of the settings, from [`config_hash`].
+/// * `mode` - How the destination is written.
+/// * `manifest` - The manifest of the destination directory.
+///
+/// # Returns
+///
+/// * `Result<FileReport>` - The report entry of the article.
+fn convert_article(
+    dest_dir: &str,
+    file: &Path,
+    frontmatter_config: Option<&ConfigFile>,
+    image_links: Option<&ImageLinks>,
+    config_hash: &str,
+    mode: OutputMode,
+    manifest: &mut Manifest,
+) -> Result<FileReport> {
+    let started = Instant::now();
+    let output_file_path = Path::new(dest_dir).join(file.file_name().unwrap());
+    let key = manifest_key(Path::new(dest_dir), &output_file_path);
+
+    // Skip the article if it is unchanged, reporting the warnings of its conversion again
+    let source_hash = hash_bytes(&fs::read(file).with_path(file)?);
+    let previous = manifest
+        .files
+        .get(&key)
+        .filter(|entry| entry.is_unchanged(&source_hash, Some(config_hash), &output_file_path));
+    if let Some(previous) = previous {
+        let mut report = FileReport::new(FileKind::Article, FileStatus::Unchanged, file);
+        report.destination = Some(output_file_path);
+        report.action = Some(Action::Unchanged);
+        report.warnings = previous.warnings.clone();
+        return Ok(report.timed(started));
+    }
+
+    // Convert the article and record its output, unless nothing is written
+    let report = copy_markdown_file(dest_dir, file, frontmatter_config, image_links, mode)?;
+    if report.destination.is_some() && !mode.dry_run {
+        let output = fs::read(&output_file_path).with_path(&output_file_path)?;
+        let mut entry = ManifestEntry::new(
+            file,
+            source_hash,
+            Some(config_hash.to_string()),
+            hash_bytes(&output),
+        );
+        entry.warnings = report.warnings.clone();
+        manifest.files.insert(key, entry);
+    }
+
+    Ok(report)
+}
+
+/// Returns the hash of the settings the articles are converted with.
+///
+/// # Arguments
+///
+/// * `config` - The configuration file, if any.
+/// * `image_links` - The settings for rewriting `/images/...` URLs, if any.
+///
+/// # Returns
+///
+/// * `Result<String>` - The SHA-256 hash of the settings.
+fn config_hash(config: Option<&ConfigFile>, image_links: Option<&ImageLinks>) -> Result<String> {
+    // Serialize through a JSON value to sort the keys of maps
+    let images = image_links.map(|links| (&links.prefix, &links.src_images_dir));
+    let settings = serde_json::to_value((config, images)).map_err(|e| Error::transform("", e))?;
+
+    Ok(hash_bytes(settings.to_string().as_bytes()))
 }
@@ -0,0 +1,261 @@
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use super::frontmatter::Frontmatter;
+use crate::error::{Error, Result};
+
+/// The UTC offset of Zenn's `published_at` (Japan Standard Time).
+const JST_OFFSET_SECONDS: i32 = 9 * 60 * 60;
+
+/// Which articles are emitted, based on their Zenn `published` flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PublicationPolicy {
+    /// Emit every article.
+    #[default]
+    All,
+    /// Emit only articles with `published: true` whose `published_at` has passed.
+    PublishedOnly,
+    /// Emit only articles with `published: false`.
+    DraftsOnly,
+}
+
+/// `PublicationOptions` controls which articles are emitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PublicationOptions {
+    /// The publication policy.
+    pub policy: PublicationPolicy,
+    /// The date and time `published_at` is compared with (e.g. `2024-08-04 09:00`,
+    /// interpreted in JST, or an RFC 3339 timestamp). Defaults to the current time.
+    pub now: Option<String>,
+}
+
+/// The reason an article was not emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The article is a draft (`published: false`).
+    Draft,
+    /// The article is published (`published: true`) but only drafts are emitted.
+    Published,
+    /// The article is scheduled for a later `published_at`.
+    Scheduled(String),
+    /// The `published_at` of the article cannot be parsed, so its schedule is unknown.
+    InvalidDate(String),
+    /// The article broke a validation rule whose policy is `skip`.
+    Invalid(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Draft => write!(f, "draft"),
+            SkipReason::Published => write!(f, "published"),
+            SkipReason::Scheduled(published_at) => write!(f, "scheduled for {}", published_at),
+            SkipReason::InvalidDate(published_at) => {
+                write!(f, "invalid `published_at` {:?}", published_at)
+            }
+            SkipReason::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Decides whether an article is emitted under the publication options.
+///
+/// An article is a draft unless its frontmatter has `published: true`. With the
+/// `published-only` policy, a published article whose `published_at` is later
+/// than `now` is held back as scheduled, and one whose `published_at` cannot be
+/// parsed is held back as well, as it may not be due yet.
+///
+/// # Arguments
+///
+/// * `data` - The frontmatter of the article.
+/// * `options` - The publication options.
+/// * `now` - The date and time `published_at` is compared with.
+///
+/// # Returns
+///
+/// * `Option<SkipReason>` - `None` if the article is emitted, otherwise the reason it is skipped.
+pub fn check_publication(
+    data: &Frontmatter,
+    options: &PublicationOptions,
+    now: DateTime<FixedOffset>,
+) -> Option<SkipReason> {
+    let published = data
+        .get("published")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    match options.policy {
+        PublicationPolicy::All => None,
+        PublicationPolicy::DraftsOnly => published.then_some(SkipReason::Published),
+        PublicationPolicy::PublishedOnly if !published => Some(SkipReason::Draft),
+        PublicationPolicy::PublishedOnly => {
+            // Hold back articles scheduled for later, or whose schedule is unknown
+            let value = data.get("published_at").filter(|value| !value.is_null())?;
+            let Some(published_at) = value.as_str() else {
+                let published_at = serde_yaml::to_string(value).unwrap_or_default();
+                return Some(SkipReason::InvalidDate(published_at.trim().to_string()));
+            };
+            match parse_datetime(published_at) {
+                Some(at) if at > now => Some(SkipReason::Scheduled(published_at.to_string())),
+                Some(_) => None,
+                None => Some(SkipReason::InvalidDate(published_at.to_string())),
+            }
+        }
+    }
+}
+
+/// Resolves the `now` publication option, defaulting to the current time.
+///
+/// # Arguments
+///
+/// * `options` - The publication options.
+///
+/// # Returns
+///
+/// * `Result<DateTime<FixedOffset>>` - The resolved time, or an `Error::Config` without a path if `now` cannot be parsed.
+pub fn resolve_now(options: &PublicationOptions) -> Result<DateTime<FixedOffset>> {
+    match &options.now {
+        Some(now) => parse_datetime(now)
+            .ok_or_else(|| Error::config("", format!("Invalid `now` date: {:?}", now))),
+        None => Ok(Utc::now().fixed_offset()),
+    }
+}
+
+/// Parses a Zenn `published_at` value (`YYYY-MM-DD hh:mm` in JST) or an RFC 3339 timestamp.
+///
+/// # Arguments
+///
+/// * `value` - The date string to parse.
+///
+/// # Returns
+///
+/// * `Option<DateTime<FixedOffset>>` - The parsed date, or `None` if it is not a supported format.
+pub fn parse_datetime(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime);
+    }
+
+    // Zenn dates without an offset are in JST
+    let jst = FixedOffset::east_opt(JST_OFFSET_SECONDS)?;
+    let naive = [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })?;
+
+    naive.and_local_timezone(jst).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter(yaml: &str) -> Frontmatter {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_check_publication() {
+        let now = parse_datetime("2024-08-04 09:00").unwrap();
+        let published = frontmatter("published: true");
+        let draft = frontmatter("published: false");
+        let unset = frontmatter("title: Untitled");
+
+        let all = PublicationOptions::default();
+        assert_eq!(check_publication(&published, &all, now), None);
+        assert_eq!(check_publication(&draft, &all, now), None);
+
+        let published_only = PublicationOptions {
+            policy: PublicationPolicy::PublishedOnly,
+            now: None,
+        };
+        assert_eq!(check_publication(&published, &published_only, now), None);
+        assert_eq!(
+            check_publication(&draft, &published_only, now),
+            Some(SkipReason::Draft)
+        );
+        assert_eq!(
+            check_publication(&unset, &published_only, now),
+            Some(SkipReason::Draft)
+        );
+
+        let drafts_only = PublicationOptions {
+            policy: PublicationPolicy::DraftsOnly,
+            now: None,
+        };
+        assert_eq!(
+            check_publication(&published, &drafts_only, now),
+            Some(SkipReason::Published)
+        );
+        assert_eq!(check_publication(&draft, &drafts_only, now), None);
+    }
+
+    #[test]
+    fn test_check_publication_with_schedule() {
+        let options = PublicationOptions {
+            policy: PublicationPolicy::PublishedOnly,
+            now: None,
+        };
+        let scheduled = frontmatter("published: true\npublished_at: 2024-08-04 10:00");
+        let past = frontmatter("published: true\npublished_at: 2024-08-04 08:59");
+
+        let now = parse_datetime("2024-08-04 09:00").unwrap();
+        assert_eq!(
+            check_publication(&scheduled, &options, now),
+            Some(SkipReason::Scheduled("2024-08-04 10:00".to_string()))
+        );
+        assert_eq!(check_publication(&past, &options, now), None);
+
+        // The schedule is compared in JST
+        let now = parse_datetime("2024-08-04T01:00:00Z").unwrap();
+        assert_eq!(check_publication(&scheduled, &options, now), None);
+
+        // An unparseable date is held back rather than emitted
+        let invalid = frontmatter("published: true\npublished_at: next week");
+        assert_eq!(
+            check_publication(&invalid, &options, now),
+            Some(SkipReason::InvalidDate("next week".to_string()))
+        );
+        let invalid = frontmatter("published: true\npublished_at: 20240804");
+        assert_eq!(
+            check_publication(&invalid, &options, now),
+            Some(SkipReason::InvalidDate("20240804".to_string()))
+        );
+        assert_eq!(
+            SkipReason::InvalidDate("next week".to_string()).to_string(),
+            "invalid `published_at` \"next week\""
+        );
+    }
+
+    #[test]
+    fn test_resolve_now() {
+        let options = PublicationOptions {
+            now: Some("2024-08-04".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_now(&options).unwrap().to_rfc3339(),
+            "2024-08-04T00:00:00+09:00"
+        );
+
+        let options = PublicationOptions {
+            now: Some("tomorrow".to_string()),
+            ..Default::default()
+        };
+        assert!(resolve_now(&options).is_err());
+    }
+}
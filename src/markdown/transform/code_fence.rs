@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    lines::{scan_lines, LineKind},
+    TransformOptions,
+};
+
+/// How Zenn ```` ```diff lang ```` code blocks are converted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStyle {
+    /// Convert to a `lang` block using VitePress's `// [!code ++]` / `// [!code --]` notation.
+    #[default]
+    Notation,
+    /// Convert to a plain `diff` block, dropping the language.
+    Diff,
+}
+
+/// Rewrites Zenn code fence info strings into VitePress syntax.
+///
+/// ```` ```lang:filename ```` becomes ```` ```lang [filename] ```` and
+/// ```` ```diff lang ```` becomes either a `lang` block with VitePress diff
+/// notation or a plain `diff` block, depending on the options.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to convert.
+/// * `options` - The transform options.
+///
+/// # Returns
+///
+/// * `String` - The body with code fences rewritten.
+pub fn convert_code_fences(body: &str, options: &TransformOptions) -> String {
+    let mut output = String::with_capacity(body.len());
+
+    // The language and indentation of the open diff block, if using the notation
+    let mut diff_block: Option<(String, String)> = None;
+
+    for line in scan_lines(body) {
+        match line.kind {
+            LineKind::FenceOpen => {
+                let (rewritten, diff_lang) = convert_fence_line(line.content, options);
+                output.push_str(&rewritten);
+
+                // Remember the language when the lines must be rewritten with the notation
+                if let Some(lang) = diff_lang {
+                    let indent =
+                        &line.content[..line.content.len() - line.content.trim_start().len()];
+                    diff_block = Some((lang, indent.to_string()));
+                }
+            }
+            LineKind::Code => match &diff_block {
+                Some((lang, indent)) => {
+                    output.push_str(&convert_diff_line(line.content, lang, indent))
+                }
+                None => output.push_str(line.content),
+            },
+            LineKind::FenceClose => {
+                diff_block = None;
+                output.push_str(line.content);
+            }
+            LineKind::Text => output.push_str(line.content),
+        }
+        output.push_str(line.ending);
+    }
+
+    output
+}
+
+/// Rewrites an opening fence line.
+///
+/// Returns the rewritten line and, when the block is a diff converted to the
+/// VitePress notation, the language of the block.
+fn convert_fence_line(line: &str, options: &TransformOptions) -> (String, Option<String>) {
+    // Split the line into indentation, fence and info string
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let fence_char = trimmed.chars().next().unwrap_or('`');
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    let fence = &trimmed[..fence_len];
+    let info = trimmed[fence_len..].trim();
+
+    // Split `lang:filename` into the language and the file name
+    let (lang, filename) = match info.split_once(':') {
+        Some((lang, filename)) if !filename.trim().is_empty() => {
+            (lang.trim(), Some(filename.trim()))
+        }
+        _ => (info, None),
+    };
+
+    // Split `diff lang` into the diff marker and the language
+    let (lang, diff_lang) = match lang.split_once(' ') {
+        Some(("diff", diff_lang)) if !diff_lang.trim().is_empty() => match options.code_diff {
+            DiffStyle::Notation => (diff_lang.trim(), Some(diff_lang.trim().to_string())),
+            DiffStyle::Diff => ("diff", None),
+        },
+        _ => (lang, None),
+    };
+
+    // Leave fences without Zenn-specific syntax untouched
+    if filename.is_none() && lang == info {
+        return (line.to_string(), None);
+    }
+
+    let rewritten = match filename {
+        Some(filename) => format!("{}{}{} [{}]", indent, fence, lang, filename),
+        None => format!("{}{}{}", indent, fence, lang),
+    };
+
+    (rewritten, diff_lang)
+}
+
+/// Rewrites a line of a diff block into the VitePress diff notation.
+fn convert_diff_line(line: &str, lang: &str, indent: &str) -> String {
+    // Code lines are indented like their fence
+    let (line_indent, code) = match line.strip_prefix(indent) {
+        Some(code) => (indent, code),
+        None => ("", line),
+    };
+
+    let (marker, code) = match code.chars().next() {
+        Some('+') => (Some("++"), &code[1..]),
+        Some('-') => (Some("--"), &code[1..]),
+        Some(' ') => (None, &code[1..]),
+        _ => (None, code),
+    };
+
+    match marker {
+        Some(marker) => format!(
+            "{}{} {}",
+            line_indent,
+            code,
+            notation_comment(lang, &format!("[!code {}]", marker))
+        ),
+        None => format!("{}{}", line_indent, code),
+    }
+}
+
+/// Wraps a VitePress notation in the line comment syntax of the language.
+fn notation_comment(lang: &str, notation: &str) -> String {
+    match lang {
+        "py" | "python" | "rb" | "ruby" | "sh" | "bash" | "zsh" | "shell" | "yaml" | "yml"
+        | "toml" | "dockerfile" | "r" | "perl" | "elixir" | "ex" => format!("# {}", notation),
+        "html" | "xml" | "vue" | "md" | "markdown" | "svg" => format!("<!-- {} -->", notation),
+        "css" | "scss" | "less" | "c" => format!("/* {} */", notation),
+        "sql" | "lua" | "haskell" | "hs" => format!("-- {}", notation),
+        _ => format!("// {}", notation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_code_fences_with_filename() {
+        let body = "```js:src/main.js\nconsole.log(1)\n```\n```rust\nfn main() {}\n```\n";
+        let result = convert_code_fences(body, &TransformOptions::default());
+
+        assert_eq!(
+            result,
+            "```js [src/main.js]\nconsole.log(1)\n```\n```rust\nfn main() {}\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_code_fences_with_diff_notation() {
+        let body = "```diff js:app.js\n-const a = 1;\n+const a = 2;\n console.log(a);\n```\n";
+        let result = convert_code_fences(body, &TransformOptions::default());
+
+        assert_eq!(
+            result,
+            "```js [app.js]\nconst a = 1; // [!code --]\nconst a = 2; // [!code ++]\nconsole.log(a);\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_code_fences_with_plain_diff() {
+        let options = TransformOptions {
+            code_diff: DiffStyle::Diff,
+            ..Default::default()
+        };
+        let body = "```diff python\n-a = 1\n+a = 2\n```\n";
+        let result = convert_code_fences(body, &options);
+
+        assert_eq!(result, "```diff\n-a = 1\n+a = 2\n```\n");
+    }
+
+    #[test]
+    fn test_convert_code_fences_with_indented_diff() {
+        let body = "  ```diff py\n  +a = 1\n  ```";
+        let result = convert_code_fences(body, &TransformOptions::default());
+
+        assert_eq!(result, "  ```py\n  a = 1 # [!code ++]\n  ```");
+    }
+
+    #[test]
+    fn test_convert_code_fences_inside_code_block() {
+        let body = "````md\n```js:main.js\n```\n````\n";
+        let result = convert_code_fences(body, &TransformOptions::default());
+
+        assert_eq!(result, body);
+    }
+}
@@ -0,0 +1,112 @@
+use super::{lines::map_text_lines, AlertContainer, TransformOptions};
+
+/// Converts Zenn `:::message` and `:::details` blocks into VitePress custom containers.
+///
+/// `:::message` becomes `::: info` and `:::message alert` becomes `::: warning`
+/// or `::: danger` depending on the options. `:::details Title` becomes
+/// `::: details Title`. The number of colons is kept as-is,
+/// so nested blocks using `::::` fences keep working in VitePress. Lines inside
+/// fenced code blocks are left untouched.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to convert.
+/// * `options` - The transform options.
+///
+/// # Returns
+///
+/// * `String` - The body with Zenn containers replaced by VitePress containers.
+pub fn convert_containers(body: &str, options: &TransformOptions) -> String {
+    map_text_lines(body, |line| {
+        convert_container_line(line, options).unwrap_or_else(|| line.to_string())
+    })
+}
+
+/// Converts a single container opening line, returning `None` if it is not a Zenn container.
+fn convert_container_line(line: &str, options: &TransformOptions) -> Option<String> {
+    // Split the line into indentation, colon fence and the rest
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let fence_len = trimmed.chars().take_while(|c| *c == ':').count();
+    if fence_len < 3 {
+        return None;
+    }
+    let fence = &trimmed[..fence_len];
+
+    // Split the rest into the container name and its arguments
+    let rest = trimmed[fence_len..].trim();
+    let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let args = args.trim();
+
+    let container = match (name, args) {
+        ("message", "") => "info".to_string(),
+        ("message", "alert") => match options.alert {
+            AlertContainer::Warning => "warning".to_string(),
+            AlertContainer::Danger => "danger".to_string(),
+        },
+        ("details", "") => "details".to_string(),
+        ("details", title) => format!("details {}", title),
+        _ => return None,
+    };
+
+    Some(format!("{}{} {}", indent, fence, container))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_containers() {
+        let body = ":::message\nInfo\n:::\n\n:::message alert\nAlert\n:::\n";
+        let result = convert_containers(body, &TransformOptions::default());
+
+        assert_eq!(result, "::: info\nInfo\n:::\n\n::: warning\nAlert\n:::\n");
+    }
+
+    #[test]
+    fn test_convert_containers_with_danger() {
+        let options = TransformOptions {
+            alert: AlertContainer::Danger,
+            ..Default::default()
+        };
+        let result = convert_containers(":::message alert\nAlert\n:::", &options);
+
+        assert_eq!(result, "::: danger\nAlert\n:::");
+    }
+
+    #[test]
+    fn test_convert_containers_with_nesting() {
+        let body = "::::message alert\n  :::message\n  Inner\n  :::\n::::\n";
+        let result = convert_containers(body, &TransformOptions::default());
+
+        assert_eq!(result, ":::: warning\n  ::: info\n  Inner\n  :::\n::::\n");
+    }
+
+    #[test]
+    fn test_convert_containers_with_details() {
+        let body = ":::details Click to expand\nHidden\n:::\n";
+        let result = convert_containers(body, &TransformOptions::default());
+
+        assert_eq!(result, "::: details Click to expand\nHidden\n:::\n");
+    }
+
+    #[test]
+    fn test_convert_containers_with_nested_details() {
+        let body = "::::details Outer\n:::message\nInner\n:::\n:::details  Inner details\nText\n:::\n::::\n";
+        let result = convert_containers(body, &TransformOptions::default());
+
+        assert_eq!(
+            result,
+            ":::: details Outer\n::: info\nInner\n:::\n::: details Inner details\nText\n:::\n::::\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_containers_inside_code_block() {
+        let body = "```md\n:::message\n:::\n```\n";
+        let result = convert_containers(body, &TransformOptions::default());
+
+        assert_eq!(result, body);
+    }
+}
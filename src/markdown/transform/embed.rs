@@ -0,0 +1,405 @@
+use serde::{Deserialize, Serialize};
+
+use super::{escape_attr, lines::map_text_lines, TransformOptions};
+
+/// How a Zenn embed directive (`@[service](arg)`) is rendered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbedRender {
+    /// Render the built-in iframe (or link card) HTML for the service.
+    Html,
+    /// Render a plain Markdown link to the embedded resource.
+    Link,
+    /// Render a Vue component tag with the given name (e.g. `<YouTube id="..." />`).
+    Component(String),
+}
+
+/// The embed services supported by Zenn.
+pub const EMBED_SERVICES: [&str; 14] = [
+    "youtube",
+    "tweet",
+    "card",
+    "github",
+    "gist",
+    "codepen",
+    "codesandbox",
+    "stackblitz",
+    "speakerdeck",
+    "slideshare",
+    "docswell",
+    "figma",
+    "jsfiddle",
+    "blueprintue",
+];
+
+/// Converts Zenn embed directives into VitePress-compatible HTML or components.
+///
+/// Each line consisting of a single `@[service](arg)` directive is replaced according
+/// to the `embeds` transform options, falling back to the built-in HTML for the
+/// service. Unknown services without a configured renderer, and arguments the built-in
+/// HTML does not support, are replaced by a plain link and reported as a warning.
+/// Lines inside fenced code blocks are left untouched.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to convert.
+/// * `options` - The transform options.
+/// * `warnings` - A list that collects warnings raised during the conversion.
+///
+/// # Returns
+///
+/// * `String` - The body with embed directives replaced.
+pub fn convert_embeds(
+    body: &str,
+    options: &TransformOptions,
+    warnings: &mut Vec<String>,
+) -> String {
+    map_text_lines(body, |line| {
+        // Keep lines that are not an embed directive as-is
+        let Some((service, arg)) = parse_embed(line) else {
+            return line.to_string();
+        };
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        // Use the configured renderer, or the built-in HTML for known services
+        let render = match options.embeds.get(service) {
+            Some(render) => render.clone(),
+            None if EMBED_SERVICES.contains(&service) => EmbedRender::Html,
+            None => {
+                warnings.push(format!(
+                    "Unknown embed service `{}` was converted to a link",
+                    service
+                ));
+                EmbedRender::Link
+            }
+        };
+
+        let rendered = match render {
+            EmbedRender::Html => render_html(service, arg).unwrap_or_else(|| {
+                warnings.push(format!(
+                    "Invalid `{}` embed `{}` was converted to a link",
+                    service, arg
+                ));
+                render_link(arg)
+            }),
+            EmbedRender::Link => render_link(arg),
+            EmbedRender::Component(name) => render_component(&name, service, arg),
+        };
+
+        format!("{}{}", indent, rendered)
+    })
+}
+
+/// Parses a line of the form `@[service](arg)`, returning the service and its argument.
+fn parse_embed(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim().strip_prefix("@[")?;
+    let (service, rest) = rest.split_once("](")?;
+    let arg = rest.strip_suffix(')')?.trim();
+
+    if service.is_empty() || arg.is_empty() {
+        return None;
+    }
+
+    Some((service, arg))
+}
+
+/// Renders the built-in HTML for a known service, or `None` if the argument is not supported.
+fn render_html(service: &str, arg: &str) -> Option<String> {
+    let src = match service {
+        "youtube" => format!(
+            "https://www.youtube-nocookie.com/embed/{}",
+            youtube_video_id(arg)?
+        ),
+        "tweet" => format!(
+            "https://platform.twitter.com/embed/Tweet.html?id={}",
+            tweet_id(arg)?
+        ),
+        "card" | "github" | "gist" => return Some(render_card(service, arg)),
+        "codepen" => arg.replacen("/pen/", "/embed/", 1),
+        "speakerdeck" => format!("https://speakerdeck.com/player/{}", arg),
+        "slideshare" => format!(
+            "https://www.slideshare.net/slideshow/embed_code/key/{}",
+            arg
+        ),
+        "figma" => format!(
+            "https://www.figma.com/embed?embed_host=share&url={}",
+            percent_encode(arg)
+        ),
+        "jsfiddle" if !arg.contains("/embedded") => {
+            format!("{}/embedded/", arg.trim_end_matches('/'))
+        }
+        "jsfiddle" => arg.to_string(),
+        "codesandbox" => codesandbox_embed_url(arg)?,
+        "stackblitz" => stackblitz_embed_url(arg)?,
+        "docswell" => docswell_embed_url(arg)?,
+        "blueprintue" if arg.starts_with("https://blueprintue.com/render/") => arg.to_string(),
+        _ => return None,
+    };
+
+    // Services rendered as video players use a 16:9 frame
+    let height = if service == "youtube" { 315 } else { 400 };
+
+    Some(format!(
+        r#"<iframe class="embed-{}" src="{}" width="100%" height="{}" frameborder="0" loading="lazy" allowfullscreen></iframe>"#,
+        service,
+        escape_attr(&src),
+        height
+    ))
+}
+
+/// Renders a link card for services that are not embeddable as an iframe.
+fn render_card(service: &str, url: &str) -> String {
+    format!(
+        r#"<a class="embed-{}" href="{}" target="_blank" rel="noopener noreferrer">{}</a>"#,
+        service,
+        escape_attr(url),
+        escape_attr(url)
+    )
+}
+
+/// Renders a plain Markdown link.
+///
+/// Only an absolute URL makes an autolink: any other argument, such as a bare video id,
+/// is rendered as escaped text so that it is not read as an HTML tag.
+fn render_link(arg: &str) -> String {
+    if is_absolute_url(arg) {
+        format!("<{}>", arg)
+    } else {
+        escape_attr(arg)
+    }
+}
+
+/// Returns whether the argument is an absolute URL that CommonMark turns into an autolink,
+/// i.e. a scheme of 2 to 32 characters followed by `:` and no spaces or angle brackets.
+fn is_absolute_url(arg: &str) -> bool {
+    let Some((scheme, _)) = arg.split_once(':') else {
+        return false;
+    };
+    (2..=32).contains(&scheme.len())
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'))
+        && !arg.contains(|c: char| c.is_whitespace() || c == '<' || c == '>')
+}
+
+/// Renders a Vue component tag, passing the argument as `id` or `url`.
+fn render_component(name: &str, service: &str, arg: &str) -> String {
+    let attr = match service {
+        "youtube" | "speakerdeck" | "slideshare" => "id",
+        _ => "url",
+    };
+
+    format!(r#"<{} {}="{}" />"#, name, attr, escape_attr(arg))
+}
+
+/// Extracts a YouTube video ID from either a bare ID or a video URL.
+fn youtube_video_id(arg: &str) -> Option<&str> {
+    let id = if let Some((_, query)) = arg.split_once("v=") {
+        query.split('&').next()?
+    } else if let Some((_, path)) = arg.split_once("youtu.be/") {
+        path.split('?').next()?
+    } else {
+        arg
+    };
+
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(id)
+}
+
+/// Extracts the status ID from a tweet URL.
+fn tweet_id(arg: &str) -> Option<&str> {
+    let (_, rest) = arg.split_once("/status/")?;
+    let id = rest.split(['?', '/']).next()?;
+
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then_some(id)
+}
+
+/// Returns the embed URL of a CodeSandbox sandbox, from its `/s/` or `/embed/` URL.
+fn codesandbox_embed_url(arg: &str) -> Option<String> {
+    let path = arg
+        .strip_prefix("https://codesandbox.io/embed/")
+        .or_else(|| arg.strip_prefix("https://codesandbox.io/s/"))?;
+
+    (!path.is_empty()).then(|| format!("https://codesandbox.io/embed/{}", path))
+}
+
+/// Returns the embed URL of a StackBlitz project, from its `/edit/` or `/github/` URL.
+fn stackblitz_embed_url(arg: &str) -> Option<String> {
+    let path = arg
+        .strip_prefix("https://stackblitz.com/edit/")
+        .or_else(|| arg.strip_prefix("https://stackblitz.com/github/"))?;
+    if path.is_empty() {
+        return None;
+    }
+
+    let separator = if arg.contains('?') { '&' } else { '?' };
+    Some(format!("{}{}embed=1", arg, separator))
+}
+
+/// Returns the embed URL of a Docswell slide, from its `/s/<user>/<slide>` URL.
+fn docswell_embed_url(arg: &str) -> Option<String> {
+    let path = arg.strip_prefix("https://www.docswell.com/s/")?;
+    let slide = path.split(['?', '#']).next()?.split('/').nth(1)?;
+    // The slide ID is followed by the optional title of the slide
+    let id = slide.split('-').next()?;
+
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then(|| format!("https://www.docswell.com/slide/{}/embed", id))
+}
+
+/// Percent-encodes a value for use as a URL query parameter.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_embeds_with_youtube() {
+        let mut warnings = Vec::new();
+        let result = convert_embeds(
+            "@[youtube](WRVsOCh907o)\n",
+            &TransformOptions::default(),
+            &mut warnings,
+        );
+
+        assert_eq!(
+            result,
+            "<iframe class=\"embed-youtube\" src=\"https://www.youtube-nocookie.com/embed/WRVsOCh907o\" width=\"100%\" height=\"315\" frameborder=\"0\" loading=\"lazy\" allowfullscreen></iframe>\n"
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_convert_embeds_with_tweet_and_card() {
+        let mut warnings = Vec::new();
+        let body =
+            "@[tweet](https://x.com/zenn_dev/status/1234567890?s=20)\n@[card](https://zenn.dev)";
+        let result = convert_embeds(body, &TransformOptions::default(), &mut warnings);
+
+        assert!(result.contains("https://platform.twitter.com/embed/Tweet.html?id=1234567890"));
+        assert!(result.contains(
+            r#"<a class="embed-card" href="https://zenn.dev" target="_blank" rel="noopener noreferrer">https://zenn.dev</a>"#
+        ));
+    }
+
+    #[test]
+    fn test_convert_embeds_with_component() {
+        let mut options = TransformOptions::default();
+        options.embeds.insert(
+            "youtube".to_string(),
+            EmbedRender::Component("YouTube".to_string()),
+        );
+        options
+            .embeds
+            .insert("codepen".to_string(), EmbedRender::Link);
+
+        let mut warnings = Vec::new();
+        let body = "@[youtube](abc)\n@[codepen](https://codepen.io/a/pen/b)";
+        let result = convert_embeds(body, &options, &mut warnings);
+
+        assert_eq!(
+            result,
+            "<YouTube id=\"abc\" />\n<https://codepen.io/a/pen/b>"
+        );
+    }
+
+    #[test]
+    fn test_convert_embeds_with_unknown_service() {
+        let mut warnings = Vec::new();
+        let result = convert_embeds(
+            "@[unknown](https://example.com)",
+            &TransformOptions::default(),
+            &mut warnings,
+        );
+
+        assert_eq!(result, "<https://example.com>");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unknown"));
+    }
+
+    #[test]
+    fn test_convert_embeds_with_invalid_argument() {
+        let mut warnings = Vec::new();
+        let result = convert_embeds(
+            "@[stackblitz](https://example.com/edit/a)",
+            &TransformOptions::default(),
+            &mut warnings,
+        );
+
+        assert_eq!(result, "<https://example.com/edit/a>");
+        assert_eq!(
+            warnings,
+            vec!["Invalid `stackblitz` embed `https://example.com/edit/a` was converted to a link"]
+        );
+    }
+
+    #[test]
+    fn test_convert_embeds_with_bare_argument() {
+        let mut warnings = Vec::new();
+        let result = convert_embeds(
+            "@[foo](abc123)\n@[youtube](<script>)",
+            &TransformOptions::default(),
+            &mut warnings,
+        );
+
+        // Only absolute URLs become autolinks, other arguments are escaped text
+        assert_eq!(result, "abc123\n&lt;script&gt;");
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_convert_embeds_inside_code_block() {
+        let mut warnings = Vec::new();
+        let body = "```\n@[youtube](abc)\n```";
+        let result = convert_embeds(body, &TransformOptions::default(), &mut warnings);
+
+        assert_eq!(result, body);
+    }
+
+    #[test]
+    fn test_render_html() {
+        assert!(render_html("codepen", "https://codepen.io/a/pen/b")
+            .unwrap()
+            .contains("https://codepen.io/a/embed/b"));
+        assert!(render_html("jsfiddle", "https://jsfiddle.net/a/b")
+            .unwrap()
+            .contains("https://jsfiddle.net/a/b/embedded/"));
+        assert!(render_html("figma", "https://www.figma.com/file/x")
+            .unwrap()
+            .contains("url=https%3A%2F%2Fwww.figma.com%2Ffile%2Fx"));
+        assert!(render_html("codesandbox", "https://codesandbox.io/s/abc")
+            .unwrap()
+            .contains(r#"src="https://codesandbox.io/embed/abc""#));
+        assert!(
+            render_html("stackblitz", "https://stackblitz.com/edit/a?file=b.ts")
+                .unwrap()
+                .contains(r#"src="https://stackblitz.com/edit/a?file=b.ts&amp;embed=1""#)
+        );
+        assert!(
+            render_html("docswell", "https://www.docswell.com/s/user/K4VE8Z-title")
+                .unwrap()
+                .contains(r#"src="https://www.docswell.com/slide/K4VE8Z/embed""#)
+        );
+        assert_eq!(render_html("youtube", "not a valid id"), None);
+        assert_eq!(
+            render_html("codesandbox", "https://example.com/s/abc"),
+            None
+        );
+        assert_eq!(render_html("blueprintue", "https://example.com"), None);
+    }
+}
@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use super::{
+    lines::{map_text_lines, scan_lines, LineKind},
+    TransformOptions,
+};
+use crate::markdown::frontmatter::Frontmatter;
+
+/// The template used for the title heading when none is configured.
+const DEFAULT_TITLE_TEMPLATE: &str = "# {title}";
+
+/// The deepest Markdown heading level.
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// When the article title is inserted as a heading at the top of the body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TitleHeading {
+    /// Always insert the title heading.
+    #[default]
+    Always,
+    /// Never insert the title heading, e.g. for themes that render the frontmatter title.
+    Never,
+    /// Insert the title heading unless the body already starts with an H1.
+    IfMissing,
+}
+
+/// Inserts the title heading at the top of an article body, according to the options.
+///
+/// The heading is rendered from the `title_template` option, where `{key}` is
+/// replaced by the frontmatter value of `key` (e.g. `# {emoji} {title}`). No heading
+/// is inserted when it renders without text, e.g. for an article without a title.
+///
+/// When the `shift_headings` option is set and a heading is inserted, every heading of
+/// the body is moved one level down so that the title is the only H1. As an H6 cannot
+/// be moved down, the headings are left as they are if the body has one, with a warning.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body of the article.
+/// * `data` - The frontmatter of the article.
+/// * `options` - The transform options.
+/// * `warnings` - A list where a warning is added if the headings cannot be shifted.
+///
+/// # Returns
+///
+/// * `String` - The body with the title heading, if inserted.
+pub fn insert_title_heading(
+    body: &str,
+    data: &Frontmatter,
+    options: &TransformOptions,
+    warnings: &mut Vec<String>,
+) -> String {
+    // Decide whether the heading is inserted
+    let insert = match options.title_heading {
+        TitleHeading::Always => true,
+        TitleHeading::Never => false,
+        TitleHeading::IfMissing => !starts_with_h1(body),
+    };
+    if !insert {
+        return body.to_string();
+    }
+
+    // Render the heading and move the existing headings down, if configured
+    let template = options
+        .title_template
+        .as_deref()
+        .unwrap_or(DEFAULT_TITLE_TEMPLATE);
+    let heading = render_template(template, data);
+    if heading.trim_start_matches('#').trim().is_empty() {
+        return body.to_string();
+    }
+    let body = if !options.shift_headings {
+        body.to_string()
+    } else if has_h6(body) {
+        warnings.push("Headings were not shifted, as the body has an H6 heading".to_string());
+        body.to_string()
+    } else {
+        shift_headings(body)
+    };
+
+    format!("{}\n{}", heading, body)
+}
+
+/// Renders a title template, replacing `{key}` with the frontmatter value of `key`.
+///
+/// Placeholders of missing keys are removed, along with the spaces that separate them
+/// from the rest of the template. The values are inserted as they are.
+fn render_template(template: &str, data: &Frontmatter) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+
+        let key = &rest[start + 1..start + end];
+        let value = match data.get(key) {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            Some(Value::Bool(b)) => b.to_string(),
+            _ => String::new(),
+        };
+        rest = &rest[start + end + 1..];
+
+        // Drop the spaces before an empty value, or after it at the start of the template
+        if value.is_empty() {
+            if output.ends_with(' ') {
+                output.truncate(output.trim_end_matches(' ').len());
+            } else {
+                rest = rest.trim_start_matches(' ');
+            }
+        }
+        output.push_str(&value);
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// Returns whether the first non-blank line of the body is an H1 heading.
+fn starts_with_h1(body: &str) -> bool {
+    scan_lines(body)
+        .iter()
+        .find(|line| !line.content.trim().is_empty())
+        .is_some_and(|line| line.kind == LineKind::Text && heading_level(line.content) == Some(1))
+}
+
+/// Returns whether the body has an H6 heading outside code blocks.
+fn has_h6(body: &str) -> bool {
+    scan_lines(body).iter().any(|line| {
+        line.kind == LineKind::Text && heading_level(line.content) == Some(MAX_HEADING_LEVEL)
+    })
+}
+
+/// Moves every ATX heading of the body one level down. The body must not have an H6.
+fn shift_headings(body: &str) -> String {
+    map_text_lines(body, |line| match heading_level(line) {
+        Some(_) => format!("#{}", line),
+        None => line.to_string(),
+    })
+}
+
+/// Returns the level of an ATX heading line (e.g. `## Title`), or `None` for other lines.
+fn heading_level(line: &str) -> Option<usize> {
+    // Headings may be indented by up to three spaces
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+
+    let rest = &line[indent..];
+    let level = rest.chars().take_while(|c| *c == '#').count();
+    let after = &rest[level..];
+    let valid = (1..=MAX_HEADING_LEVEL).contains(&level)
+        && (after.is_empty() || after.starts_with([' ', '\t']));
+    valid.then_some(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter(yaml: &str) -> Frontmatter {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_insert_title_heading() {
+        let data = frontmatter("title: Hello\nemoji: '👋'");
+        let body = "# Existing\n\nText\n";
+
+        let mut options = TransformOptions::default();
+        assert_eq!(
+            insert_title_heading(body, &data, &options, &mut Vec::new()),
+            "# Hello\n# Existing\n\nText\n"
+        );
+
+        options.title_heading = TitleHeading::Never;
+        assert_eq!(
+            insert_title_heading(body, &data, &options, &mut Vec::new()),
+            body
+        );
+
+        options.title_heading = TitleHeading::IfMissing;
+        assert_eq!(
+            insert_title_heading(body, &data, &options, &mut Vec::new()),
+            body
+        );
+        assert_eq!(
+            insert_title_heading("\n## Section\n", &data, &options, &mut Vec::new()),
+            "# Hello\n\n## Section\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_title_heading_with_template() {
+        let options = TransformOptions {
+            title_template: Some("# {emoji} {title}".to_string()),
+            ..Default::default()
+        };
+
+        let data = frontmatter("title: Hello\nemoji: '👋'");
+        assert_eq!(
+            insert_title_heading("Text", &data, &options, &mut Vec::new()),
+            "# 👋 Hello\nText"
+        );
+
+        // Missing values leave no extra spaces, and the spaces of values are kept
+        let data = frontmatter("title: 'Hello  world'");
+        assert_eq!(
+            insert_title_heading("Text", &data, &options, &mut Vec::new()),
+            "# Hello  world\nText"
+        );
+
+        let options = TransformOptions {
+            title_template: Some("{emoji} {title} {type}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            insert_title_heading("Text", &data, &options, &mut Vec::new()),
+            "Hello  world\nText"
+        );
+    }
+
+    #[test]
+    fn test_insert_title_heading_without_title() {
+        let options = TransformOptions {
+            shift_headings: true,
+            ..Default::default()
+        };
+        let data = frontmatter("emoji: '👋'");
+
+        // No empty heading is inserted, and the headings are left as they are
+        assert_eq!(
+            insert_title_heading("# One\n", &data, &options, &mut Vec::new()),
+            "# One\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_title_heading_with_shifted_headings() {
+        let options = TransformOptions {
+            shift_headings: true,
+            ..Default::default()
+        };
+        let data = frontmatter("title: Hello");
+        let body = "# One\n## Two\n##### Five\n```md\n###### Code\n```\n#hashtag\n";
+
+        let mut warnings = Vec::new();
+        assert_eq!(
+            insert_title_heading(body, &data, &options, &mut warnings),
+            "# Hello\n## One\n### Two\n###### Five\n```md\n###### Code\n```\n#hashtag\n"
+        );
+        assert!(warnings.is_empty());
+
+        // A body with an H6 is left as it is, so that H5 and H6 stay distinct
+        let body = "# One\n##### Five\n###### Six\n";
+        assert_eq!(
+            insert_title_heading(body, &data, &options, &mut warnings),
+            "# Hello\n# One\n##### Five\n###### Six\n"
+        );
+        assert_eq!(
+            warnings,
+            vec!["Headings were not shifted, as the body has an H6 heading"]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    escape_attr,
+    lines::{code_spans, scan_lines, LineKind},
+    TransformOptions,
+};
+
+/// How an image followed by a Zenn caption line is rendered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptionRender {
+    /// Render a `<figure>` with a `<figcaption>`.
+    #[default]
+    Figure,
+    /// Render a Vue component tag with the given name, passing `src`, `alt`,
+    /// `width`, `height` and `caption` as attributes.
+    Component(String),
+}
+
+/// An image parsed from Zenn's `![alt](src =WxH)` syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Image<'a> {
+    alt: &'a str,
+    src: &'a str,
+    width: Option<&'a str>,
+    height: Option<&'a str>,
+}
+
+/// Converts Zenn image size hints and captions into HTML.
+///
+/// `![alt](src =250x)` becomes `<img>` with `width`/`height` attributes. An image
+/// on its own line followed directly by an italic `*caption*` line becomes a
+/// `<figure>` with a `<figcaption>`, or the configured component. Images without
+/// a size hint or caption are left as Markdown. Lines inside fenced code blocks
+/// are left untouched.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to convert.
+/// * `options` - The transform options.
+///
+/// # Returns
+///
+/// * `String` - The body with image syntax converted.
+pub fn convert_images(body: &str, options: &TransformOptions) -> String {
+    let lines = scan_lines(body);
+    let mut output = String::with_capacity(body.len());
+
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+
+        if line.kind != LineKind::Text {
+            output.push_str(line.content);
+            output.push_str(line.ending);
+            index += 1;
+            continue;
+        }
+
+        // An image on its own line followed by a caption line becomes a figure
+        let caption = lines
+            .get(index + 1)
+            .filter(|next| next.kind == LineKind::Text)
+            .and_then(|next| parse_caption(next.content));
+        let image = parse_lone_image(line.content);
+
+        if let (Some(image), Some(caption)) = (image, caption) {
+            let indent = &line.content[..line.content.len() - line.content.trim_start().len()];
+            output.push_str(indent);
+            output.push_str(&render_figure(&image, caption, &options.image_caption));
+            output.push_str(lines[index + 1].ending);
+            index += 2;
+            continue;
+        }
+
+        output.push_str(&convert_sized_images(line.content));
+        output.push_str(line.ending);
+        index += 1;
+    }
+
+    output
+}
+
+/// Replaces every image with a size hint in a line with an `<img>` tag.
+///
+/// Images inside inline code spans are left untouched.
+fn convert_sized_images(line: &str) -> String {
+    let spans = code_spans(line);
+    let mut output = String::with_capacity(line.len());
+    let mut position = 0;
+
+    while let Some(offset) = line[position..].find("![") {
+        let start = position + offset;
+        output.push_str(&line[position..start]);
+
+        // Copy a code span as is, up to its closing backticks
+        if let Some(span) = spans.iter().find(|span| span.contains(&start)) {
+            output.push_str(&line[start..span.end]);
+            position = span.end;
+            continue;
+        }
+
+        match parse_image(&line[start..]) {
+            Some((image, len)) if image.width.is_some() || image.height.is_some() => {
+                output.push_str(&render_img(&image));
+                position = start + len;
+            }
+            _ => {
+                output.push_str("![");
+                position = start + 2;
+            }
+        }
+    }
+
+    output.push_str(&line[position..]);
+    output
+}
+
+/// Parses an image at the start of the input, returning it and the length of its syntax.
+fn parse_image(input: &str) -> Option<(Image<'_>, usize)> {
+    let rest = input.strip_prefix("![")?;
+    let alt_end = rest.find("](")?;
+    let alt = &rest[..alt_end];
+
+    let target_start = 2 + alt_end + 2;
+    let target_end = target_start + input[target_start..].find(')')?;
+    let target = input[target_start..target_end].trim();
+
+    // Split an optional ` =WxH` size hint from the URL
+    let (src, size) = match target.rsplit_once(" =") {
+        Some((src, size)) => (src.trim(), Some(size.trim())),
+        None => (target, None),
+    };
+    if src.is_empty() || src.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let (width, height) = match size {
+        Some(size) => {
+            let (width, height) = size.split_once('x')?;
+            let is_size = |value: &str| value.chars().all(|c| c.is_ascii_digit());
+            if !is_size(width) || !is_size(height) {
+                return None;
+            }
+            (
+                Some(width).filter(|w| !w.is_empty()),
+                Some(height).filter(|h| !h.is_empty()),
+            )
+        }
+        None => (None, None),
+    };
+
+    let image = Image {
+        alt,
+        src,
+        width,
+        height,
+    };
+    Some((image, target_end + 1))
+}
+
+/// Parses a line that consists of a single image.
+fn parse_lone_image(line: &str) -> Option<Image<'_>> {
+    let trimmed = line.trim();
+    let (image, len) = parse_image(trimmed)?;
+
+    (len == trimmed.len()).then_some(image)
+}
+
+/// Parses a Zenn caption line (`*caption*`), returning the caption text.
+fn parse_caption(line: &str) -> Option<&str> {
+    let caption = line.trim().strip_prefix('*')?.strip_suffix('*')?;
+
+    // Bold text (`**text**`) is not a caption
+    let is_caption = !caption.is_empty() && !caption.starts_with('*') && !caption.ends_with('*');
+    is_caption.then_some(caption.trim())
+}
+
+/// Renders the size attributes of an image.
+fn render_size_attrs(image: &Image) -> String {
+    let mut attrs = String::new();
+    if let Some(width) = image.width {
+        attrs.push_str(&format!(r#" width="{}""#, width));
+    }
+    if let Some(height) = image.height {
+        attrs.push_str(&format!(r#" height="{}""#, height));
+    }
+    attrs
+}
+
+/// Renders an `<img>` tag.
+fn render_img(image: &Image) -> String {
+    format!(
+        r#"<img src="{}" alt="{}"{}>"#,
+        escape_attr(image.src),
+        escape_attr(image.alt),
+        render_size_attrs(image)
+    )
+}
+
+/// Renders an image with its caption.
+fn render_figure(image: &Image, caption: &str, render: &CaptionRender) -> String {
+    match render {
+        CaptionRender::Figure => format!(
+            "<figure>{}<figcaption>{}</figcaption></figure>",
+            render_img(image),
+            escape_attr(caption)
+        ),
+        CaptionRender::Component(name) => format!(
+            r#"<{} src="{}" alt="{}"{} caption="{}" />"#,
+            name,
+            escape_attr(image.src),
+            escape_attr(image.alt),
+            render_size_attrs(image),
+            escape_attr(caption)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_images_with_size() {
+        let body = "![Alt](/images/a.png =250x)\nText ![B](/b.png =x100) and ![C](/c.png)\n";
+        let result = convert_images(body, &TransformOptions::default());
+
+        assert_eq!(
+            result,
+            "<img src=\"/images/a.png\" alt=\"Alt\" width=\"250\">\nText <img src=\"/b.png\" alt=\"B\" height=\"100\"> and ![C](/c.png)\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_images_with_caption() {
+        let body = "![Alt](/a.png =250x100)\n*A caption*\n\n![B](/b.png)\n**Bold**\n";
+        let result = convert_images(body, &TransformOptions::default());
+
+        assert_eq!(
+            result,
+            "<figure><img src=\"/a.png\" alt=\"Alt\" width=\"250\" height=\"100\"><figcaption>A caption</figcaption></figure>\n\n![B](/b.png)\n**Bold**\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_images_with_caption_component() {
+        let options = TransformOptions {
+            image_caption: CaptionRender::Component("Figure".to_string()),
+            ..Default::default()
+        };
+        let body = "![Alt](/a.png)\n*Caption*";
+        let result = convert_images(body, &options);
+
+        assert_eq!(
+            result,
+            "<Figure src=\"/a.png\" alt=\"Alt\" caption=\"Caption\" />"
+        );
+    }
+
+    #[test]
+    fn test_convert_images_inside_code_block() {
+        let body = "```md\n![Alt](/a.png =250x)\n*Caption*\n```";
+        let result = convert_images(body, &TransformOptions::default());
+
+        assert_eq!(result, body);
+    }
+
+    #[test]
+    fn test_convert_images_inside_code_span() {
+        let body = "Write `![Alt](/a.png =250x)` for ![B](/b.png =x100)\n";
+        let result = convert_images(body, &TransformOptions::default());
+
+        assert_eq!(
+            result,
+            "Write `![Alt](/a.png =250x)` for <img src=\"/b.png\" alt=\"B\" height=\"100\">\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_image() {
+        let (image, len) = parse_image("![A](/a.png =10x20) rest").unwrap();
+        assert_eq!(
+            image,
+            Image {
+                alt: "A",
+                src: "/a.png",
+                width: Some("10"),
+                height: Some("20"),
+            }
+        );
+        assert_eq!(len, 19);
+
+        assert_eq!(parse_image("![A](/a.png =wide)"), None);
+        assert_eq!(parse_image("![A]"), None);
+    }
+}
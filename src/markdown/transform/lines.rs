@@ -0,0 +1,231 @@
+use std::ops::Range;
+
+/// Classification of a single line of a Markdown body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// Regular Markdown text outside of any fenced code block.
+    Text,
+    /// The opening fence of a code block (e.g. ```` ```js ````).
+    FenceOpen,
+    /// A line inside a fenced code block.
+    Code,
+    /// The closing fence of a code block.
+    FenceClose,
+}
+
+/// A single line of a Markdown body, split from its line ending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// The line content without its line ending.
+    pub content: &'a str,
+    /// The line ending (`"\n"`, `"\r\n"` or `""` for the last line).
+    pub ending: &'a str,
+    /// Whether the line is text, code or a code fence.
+    pub kind: LineKind,
+}
+
+/// Splits a Markdown body into lines and classifies each of them.
+///
+/// Fenced code blocks (```` ``` ```` or `~~~`) are tracked so that transforms
+/// can leave their contents untouched. Joining `content` and `ending` of every
+/// returned line reproduces the input exactly.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to scan.
+///
+/// # Returns
+///
+/// * `Vec<Line>` - The classified lines of the body.
+pub fn scan_lines(body: &str) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+
+    // The fence character and length of the currently open code block, if any
+    let mut open_fence: Option<(char, usize)> = None;
+
+    for raw in body.split_inclusive('\n') {
+        // Separate the line ending from the content
+        let content = raw.trim_end_matches(['\n', '\r']);
+        let ending = &raw[content.len()..];
+
+        let kind = match open_fence {
+            Some((fence_char, fence_len)) => {
+                if is_closing_fence(content, fence_char, fence_len) {
+                    open_fence = None;
+                    LineKind::FenceClose
+                } else {
+                    LineKind::Code
+                }
+            }
+            None => match parse_opening_fence(content) {
+                Some(fence) => {
+                    open_fence = Some(fence);
+                    LineKind::FenceOpen
+                }
+                None => LineKind::Text,
+            },
+        };
+
+        lines.push(Line {
+            content,
+            ending,
+            kind,
+        });
+    }
+
+    lines
+}
+
+/// Applies `f` to every line outside of fenced code blocks and reassembles the body.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to transform.
+/// * `f` - A function mapping the content of a text line to its replacement.
+///
+/// # Returns
+///
+/// * `String` - The transformed body with line endings preserved.
+pub fn map_text_lines(body: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut output = String::with_capacity(body.len());
+
+    for line in scan_lines(body) {
+        if line.kind == LineKind::Text {
+            output.push_str(&f(line.content));
+        } else {
+            output.push_str(line.content);
+        }
+        output.push_str(line.ending);
+    }
+
+    output
+}
+
+/// Returns the byte ranges of the inline code spans of a line.
+///
+/// A span opens with a run of backticks and closes at the next run of the same length.
+/// A run without a matching closing run is plain text.
+///
+/// # Arguments
+///
+/// * `line` - The content of a text line.
+///
+/// # Returns
+///
+/// * `Vec<Range<usize>>` - The spans, including their backticks, in order.
+pub fn code_spans(line: &str) -> Vec<Range<usize>> {
+    // Collect the runs of backticks with their start and length
+    let bytes = line.as_bytes();
+    let mut runs = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'`' {
+            let start = index;
+            while index < bytes.len() && bytes[index] == b'`' {
+                index += 1;
+            }
+            runs.push((start, index - start));
+        } else {
+            index += 1;
+        }
+    }
+
+    // Pair each opening run with the next run of the same length
+    let mut spans = Vec::new();
+    let mut opening = 0;
+    while opening < runs.len() {
+        let (start, length) = runs[opening];
+        match runs[opening + 1..].iter().position(|run| run.1 == length) {
+            Some(offset) => {
+                let closing = opening + 1 + offset;
+                spans.push(start..runs[closing].0 + length);
+                opening = closing + 1;
+            }
+            None => opening += 1,
+        }
+    }
+
+    spans
+}
+
+/// Parses the opening fence of a code block, returning its character and length.
+fn parse_opening_fence(content: &str) -> Option<(char, usize)> {
+    let trimmed = content.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+
+    if fence_len < 3 {
+        return None;
+    }
+
+    // A backtick fence must not contain backticks in its info string
+    if fence_char == '`' && trimmed[fence_len..].contains('`') {
+        return None;
+    }
+
+    Some((fence_char, fence_len))
+}
+
+/// Returns whether the line closes a code block opened with the given fence.
+fn is_closing_fence(content: &str, fence_char: char, fence_len: usize) -> bool {
+    let trimmed = content.trim();
+    trimmed.len() >= fence_len && trimmed.chars().all(|c| c == fence_char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_lines() {
+        let body = "text\n```js\n:::message\n```\n:::message\r\nlast";
+        let kinds: Vec<LineKind> = scan_lines(body).iter().map(|line| line.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LineKind::Text,
+                LineKind::FenceOpen,
+                LineKind::Code,
+                LineKind::FenceClose,
+                LineKind::Text,
+                LineKind::Text,
+            ]
+        );
+
+        // Joining the lines reproduces the input exactly
+        let joined: String = scan_lines(body)
+            .iter()
+            .map(|line| format!("{}{}", line.content, line.ending))
+            .collect();
+        assert_eq!(joined, body);
+    }
+
+    #[test]
+    fn test_scan_lines_with_nested_fences() {
+        // A longer fence is only closed by a fence of at least the same length
+        let body = "````md\n```js\n```\n````\n~~~\n```\n~~~\n";
+        let kinds: Vec<LineKind> = scan_lines(body).iter().map(|line| line.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LineKind::FenceOpen,
+                LineKind::Code,
+                LineKind::Code,
+                LineKind::FenceClose,
+                LineKind::FenceOpen,
+                LineKind::Code,
+                LineKind::FenceClose,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map_text_lines() {
+        let body = "a\n```\na\n```\na";
+        let result = map_text_lines(body, |line| line.to_uppercase());
+
+        assert_eq!(result, "A\n```\na\n```\nA");
+    }
+}
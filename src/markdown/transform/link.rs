@@ -0,0 +1,279 @@
+use std::path::{Component, Path, PathBuf};
+
+use super::lines::{code_spans, map_text_lines};
+use crate::markdown::config::PathsConfig;
+
+/// The absolute URL prefix Zenn uses for images.
+const ZENN_IMAGES_PREFIX: &str = "/images/";
+
+/// `ImageLinks` describes how Zenn's `/images/...` URLs are rewritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageLinks {
+    /// The public URL prefix that replaces `/images` (e.g. `/blog/assets/zenn`).
+    pub prefix: String,
+    /// The Zenn images directory used to verify that each linked image exists.
+    pub src_images_dir: PathBuf,
+}
+
+impl ImageLinks {
+    /// Creates `ImageLinks` for the given source and destination images directories.
+    ///
+    /// The public URL prefix is the `image_prefix` path option if set, otherwise
+    /// it is derived from the part of `dest_images_dir` below VitePress's `public`
+    /// directory. The `base` path option is prepended in both cases.
+    ///
+    /// # Arguments
+    ///
+    /// * `src_images_dir` - The Zenn images directory.
+    /// * `dest_images_dir` - The VitePress directory the images are copied to.
+    /// * `paths` - The path options of the configuration file.
+    ///
+    /// # Returns
+    ///
+    /// * `ImageLinks` - The resolved image link settings.
+    pub fn new(src_images_dir: &str, dest_images_dir: &str, paths: &PathsConfig) -> Self {
+        // Use the configured prefix, or derive it from the destination directory
+        let public_path = paths
+            .image_prefix
+            .clone()
+            .unwrap_or_else(|| public_url_path(Path::new(dest_images_dir)));
+
+        // Prepend the site base path, if any
+        let base = paths.base.as_deref().unwrap_or("").trim_matches('/');
+        let public_path = public_path.trim_matches('/');
+        let prefix = [base, public_path]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .fold(String::new(), |prefix, part| format!("{}/{}", prefix, part));
+
+        ImageLinks {
+            prefix,
+            src_images_dir: PathBuf::from(src_images_dir),
+        }
+    }
+}
+
+/// Rewrites Zenn `/images/...` URLs in Markdown links and HTML attributes.
+///
+/// Every rewritten image is checked against the Zenn images directory and a
+/// warning is raised for images that do not exist. Lines inside fenced code
+/// blocks and URLs inside inline code spans are left untouched.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body to convert.
+/// * `links` - The image link settings.
+/// * `warnings` - A list that collects warnings raised during the conversion.
+///
+/// # Returns
+///
+/// * `String` - The body with image URLs rewritten.
+pub fn rewrite_image_links(body: &str, links: &ImageLinks, warnings: &mut Vec<String>) -> String {
+    map_text_lines(body, |line| {
+        let mut output = String::with_capacity(line.len());
+        let mut rest = line;
+
+        while let Some(start) = find_image_url(rest) {
+            // Copy everything before the URL and skip the `/images/` prefix
+            output.push_str(&rest[..start]);
+            rest = &rest[start + ZENN_IMAGES_PREFIX.len()..];
+
+            // The image path ends at the closing delimiter of the link or attribute
+            let end = rest
+                .find(|c: char| c == ')' || c == '"' || c == '\'' || c.is_whitespace())
+                .unwrap_or(rest.len());
+            let rel_path = &rest[..end];
+
+            // Verify that the image exists in the Zenn images directory
+            let file_path = rel_path.split(['?', '#']).next().unwrap_or(rel_path);
+            if !links.src_images_dir.join(file_path).is_file() {
+                warnings.push(format!(
+                    "Image `{}{}` was not found in {:?}",
+                    ZENN_IMAGES_PREFIX, file_path, links.src_images_dir
+                ));
+            }
+
+            output.push_str(&links.prefix);
+            output.push('/');
+            output.push_str(rel_path);
+            rest = &rest[end..];
+        }
+
+        output.push_str(rest);
+        output
+    })
+}
+
+/// Finds the start of the next `/images/...` URL used as a link target or HTML attribute.
+///
+/// URLs inside inline code spans are left out, as they are shown rather than linked.
+fn find_image_url(line: &str) -> Option<usize> {
+    let spans = code_spans(line);
+    ["](", "src=\"", "href=\"", "src='", "href='"]
+        .iter()
+        .filter_map(|marker| {
+            line.match_indices(marker)
+                .map(|(index, _)| index + marker.len())
+                .filter(|start| !spans.iter().any(|span| span.contains(start)))
+                .find(|start| line[*start..].starts_with(ZENN_IMAGES_PREFIX))
+        })
+        .min()
+}
+
+/// Derives the public URL path of a directory below VitePress's `public` directory.
+///
+/// Falls back to Zenn's `/images` when the directory is not below a `public` directory.
+fn public_url_path(dest_images_dir: &Path) -> String {
+    url_path_below(dest_images_dir, "public")
+        .unwrap_or_else(|| ZENN_IMAGES_PREFIX.trim_end_matches('/').to_string())
+}
+
+/// Derives the URL path of a directory from its components below the last `root` component.
+///
+/// For example, `press/docs/books` below `docs` gives `/books`.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to derive the URL path from.
+/// * `root` - The name of the directory served as the site root (e.g. `docs` or `public`).
+///
+/// # Returns
+///
+/// * `Option<String>` - The URL path, or `None` if `dir` is not below a `root` directory.
+pub fn url_path_below(dir: &Path, root: &str) -> Option<String> {
+    let components: Vec<&str> = dir
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    let index = components.iter().rposition(|name| *name == root)?;
+    Some(format!("/{}", components[index + 1..].join("/")))
+}
+
+/// Derives the URL path of a directory served by VitePress.
+///
+/// The path below VitePress's `docs` directory is used when possible, otherwise
+/// the URL is the name of the directory (e.g. `press/articles` gives `/articles`).
+///
+/// # Arguments
+///
+/// * `dir` - The VitePress directory where pages are placed.
+///
+/// # Returns
+///
+/// * `String` - The URL path of the directory.
+pub fn docs_url_path(dir: &Path) -> String {
+    url_path_below(dir, "docs").unwrap_or_else(|| {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+        format!("/{}", name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_image_links_new() {
+        let mut paths = PathsConfig::default();
+
+        let links = ImageLinks::new("zenn/images", "docs/public/images", &paths);
+        assert_eq!(links.prefix, "/images");
+
+        let links = ImageLinks::new("zenn/images", "docs/public/assets/zenn/", &paths);
+        assert_eq!(links.prefix, "/assets/zenn");
+
+        let links = ImageLinks::new("zenn/images", "docs/public", &paths);
+        assert_eq!(links.prefix, "");
+
+        paths.base = Some("/blog/".to_string());
+        let links = ImageLinks::new("zenn/images", "docs/public/assets", &paths);
+        assert_eq!(links.prefix, "/blog/assets");
+
+        paths.image_prefix = Some("/static/img".to_string());
+        let links = ImageLinks::new("zenn/images", "docs/public/assets", &paths);
+        assert_eq!(links.prefix, "/blog/static/img");
+    }
+
+    #[test]
+    fn test_url_path_below() {
+        assert_eq!(
+            url_path_below(Path::new("press/docs/books"), "docs"),
+            Some("/books".to_string())
+        );
+        assert_eq!(
+            url_path_below(Path::new("press/docs"), "docs"),
+            Some("/".to_string())
+        );
+        assert_eq!(url_path_below(Path::new("press/books"), "docs"), None);
+    }
+
+    #[test]
+    fn test_docs_url_path() {
+        assert_eq!(docs_url_path(Path::new("press/docs/articles")), "/articles");
+        assert_eq!(docs_url_path(Path::new("press/entries/")), "/entries");
+    }
+
+    #[test]
+    fn test_rewrite_image_links() {
+        let src_dir = tempdir().unwrap();
+        fs::create_dir_all(src_dir.path().join("article")).unwrap();
+        fs::write(src_dir.path().join("article/a.png"), "").unwrap();
+
+        let links = ImageLinks {
+            prefix: "/blog/assets".to_string(),
+            src_images_dir: src_dir.path().to_path_buf(),
+        };
+
+        let body = "![A](/images/article/a.png =250x)\n<img src=\"/images/article/a.png\">\n[Link](https://example.com/images/x.png)\n```\n![A](/images/article/a.png)\n```\n";
+        let mut warnings = Vec::new();
+        let result = rewrite_image_links(body, &links, &mut warnings);
+
+        assert_eq!(
+            result,
+            "![A](/blog/assets/article/a.png =250x)\n<img src=\"/blog/assets/article/a.png\">\n[Link](https://example.com/images/x.png)\n```\n![A](/images/article/a.png)\n```\n"
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_image_links_in_code_spans() {
+        let src_dir = tempdir().unwrap();
+        let links = ImageLinks {
+            prefix: "/assets".to_string(),
+            src_images_dir: src_dir.path().to_path_buf(),
+        };
+
+        let body = "Write `![A](/images/a.png)` or ``<img src=\"/images/b.png\">``, not ` ![C](/images/c.png)\n";
+        let mut warnings = Vec::new();
+        let result = rewrite_image_links(body, &links, &mut warnings);
+
+        assert_eq!(
+            result,
+            "Write `![A](/images/a.png)` or ``<img src=\"/images/b.png\">``, not ` ![C](/assets/c.png)\n"
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("/images/c.png"));
+    }
+
+    #[test]
+    fn test_rewrite_image_links_with_missing_image() {
+        let src_dir = tempdir().unwrap();
+        let links = ImageLinks {
+            prefix: "/images".to_string(),
+            src_images_dir: src_dir.path().to_path_buf(),
+        };
+
+        let mut warnings = Vec::new();
+        let result = rewrite_image_links("![A](/images/missing.png)", &links, &mut warnings);
+
+        assert_eq!(result, "![A](/images/missing.png)");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("/images/missing.png"));
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use code_fence::{convert_code_fences, DiffStyle};
+use container::convert_containers;
+use embed::{convert_embeds, EmbedRender};
+use heading::TitleHeading;
+use image::{convert_images, CaptionRender};
+use link::{rewrite_image_links, ImageLinks};
+
+pub mod code_fence;
+pub mod container;
+pub mod embed;
+pub mod heading;
+pub mod image;
+pub mod lines;
+pub mod link;
+
+/// The VitePress container used for Zenn `:::message alert` blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertContainer {
+    /// Convert to `::: warning`.
+    #[default]
+    Warning,
+    /// Convert to `::: danger`.
+    Danger,
+}
+
+/// `TransformOptions` controls how Zenn-specific Markdown syntax in the
+/// article body is converted to VitePress syntax.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransformOptions {
+    /// The container used for `:::message alert` blocks.
+    pub alert: AlertContainer,
+
+    /// Renderers for `@[service](arg)` embeds, keyed by service name.
+    /// Services not listed here use their built-in HTML.
+    pub embeds: HashMap<String, EmbedRender>,
+
+    /// How ```` ```diff lang ```` code blocks are converted.
+    pub code_diff: DiffStyle,
+
+    /// How an image followed by a `*caption*` line is rendered.
+    pub image_caption: CaptionRender,
+
+    /// When the article title is inserted as a heading at the top of the body.
+    pub title_heading: TitleHeading,
+
+    /// The template of the title heading, where `{key}` is replaced by the frontmatter
+    /// value of `key`. Defaults to `# {title}`.
+    pub title_template: Option<String>,
+
+    /// Whether the headings of the body are moved one level down when the title is inserted.
+    pub shift_headings: bool,
+}
+
+/// `TransformedBody` holds a converted article body together with
+/// the warnings raised while converting it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransformedBody {
+    /// The converted body.
+    pub body: String,
+    /// Warnings about syntax that could not be converted faithfully.
+    pub warnings: Vec<String>,
+}
+
+/// Converts the body of a Zenn article into VitePress-compatible Markdown.
+///
+/// # Arguments
+///
+/// * `body` - The article body without its frontmatter.
+/// * `options` - The transform options.
+/// * `image_links` - Optional settings for rewriting `/images/...` URLs.
+///
+/// # Returns
+///
+/// * `TransformedBody` - The converted body and any warnings.
+pub fn transform_body(
+    body: &str,
+    options: &TransformOptions,
+    image_links: Option<&ImageLinks>,
+) -> TransformedBody {
+    let mut warnings = Vec::new();
+
+    // Rewrite `/images/...` URLs to the destination images directory
+    let body = match image_links {
+        Some(links) => rewrite_image_links(body, links, &mut warnings),
+        None => body.to_string(),
+    };
+
+    // Rewrite `lang:filename` and `diff lang` code fences
+    let body = convert_code_fences(&body, options);
+
+    // Convert `:::message` and `:::details` blocks into VitePress custom containers
+    let body = convert_containers(&body, options);
+
+    // Convert `@[service](arg)` embeds into HTML or components
+    let body = convert_embeds(&body, options, &mut warnings);
+
+    // Convert `![alt](src =WxH)` size hints and captions into HTML
+    let body = convert_images(&body, options);
+
+    TransformedBody { body, warnings }
+}
+
+/// Escapes a value for use inside a double-quoted HTML attribute.
+fn escape_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
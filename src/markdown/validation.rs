@@ -0,0 +1,398 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use super::{
+    config::ConfigFile,
+    copy::merge_frontmatter,
+    frontmatter::{parse_frontmatter, Frontmatter},
+};
+
+/// The maximum number of topics Zenn allows on an article.
+const MAX_TOPICS: usize = 5;
+
+/// The article types Zenn accepts.
+const ARTICLE_TYPES: [&str; 2] = ["tech", "idea"];
+
+/// The minimum and maximum length of a Zenn article slug.
+const SLUG_LENGTH: std::ops::RangeInclusive<usize> = 12..=50;
+
+/// What happens to an article that breaks a validation rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationPolicy {
+    /// Ignore the rule.
+    Off,
+    /// Print a warning and convert the article anyway.
+    #[default]
+    Warn,
+    /// Leave the article out and list it as skipped.
+    Skip,
+    /// Leave the article out and fail the conversion.
+    Error,
+}
+
+/// `ValidationOptions` sets the policy of each validation rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    /// The keys every article must have.
+    pub required_keys: Vec<String>,
+    /// The policy for articles missing a required key.
+    pub required: ValidationPolicy,
+    /// The policy for articles with more than 5 topics.
+    pub topics: ValidationPolicy,
+    /// The policy for articles whose `type` is not `tech` or `idea`.
+    #[serde(rename = "type")]
+    pub article_type: ValidationPolicy,
+    /// The policy for articles whose slug is not 12 to 50 characters of `a-z`, `0-9`, `_` and `-`.
+    pub slug: ValidationPolicy,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            required_keys: ["title", "emoji", "type", "topics"]
+                .iter()
+                .map(|key| key.to_string())
+                .collect(),
+            required: ValidationPolicy::Error,
+            topics: ValidationPolicy::Warn,
+            article_type: ValidationPolicy::Warn,
+            slug: ValidationPolicy::Warn,
+        }
+    }
+}
+
+/// A validation rule broken by an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// The policy of the broken rule.
+    pub policy: ValidationPolicy,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// `ValidationReport` holds the outcome of validating a list of articles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The articles to convert.
+    pub valid: Vec<PathBuf>,
+    /// The articles left out by a `skip` rule, with the issue that caused it.
+    pub skipped: Vec<(PathBuf, ValidationIssue)>,
+    /// The articles that broke an `error` rule, with their error-level issues.
+    pub failed: Vec<(PathBuf, Vec<ValidationIssue>)>,
+    /// The issues of `warn` rules, for articles that are converted.
+    pub warnings: Vec<(PathBuf, ValidationIssue)>,
+}
+
+/// Validates the frontmatter and slug of an article against Zenn's rules.
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the article (its file name without extension).
+/// * `data` - The frontmatter of the article.
+/// * `options` - The validation options.
+///
+/// # Returns
+///
+/// * `Vec<ValidationIssue>` - The broken rules, excluding those whose policy is `off`.
+pub fn validate_article(
+    slug: &str,
+    data: &Frontmatter,
+    options: &ValidationOptions,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut check = |policy: ValidationPolicy, failed: bool, message: String| {
+        if failed && policy != ValidationPolicy::Off {
+            issues.push(ValidationIssue { policy, message });
+        }
+    };
+
+    // Check the required keys
+    for key in &options.required_keys {
+        let missing = data.get(key).is_none_or(Value::is_null);
+        check(
+            options.required,
+            missing,
+            format!("Missing required key `{}`", key),
+        );
+    }
+
+    // Check the number of topics
+    let topics = data
+        .get("topics")
+        .and_then(Value::as_sequence)
+        .map_or(0, Vec::len);
+    check(
+        options.topics,
+        topics > MAX_TOPICS,
+        format!("Too many topics ({}, at most {})", topics, MAX_TOPICS),
+    );
+
+    // Check the article type
+    if let Some(article_type) = data.get("type") {
+        let valid = article_type
+            .as_str()
+            .is_some_and(|t| ARTICLE_TYPES.contains(&t));
+        check(
+            options.article_type,
+            !valid,
+            format!(
+                "Invalid type {} (expected `tech` or `idea`)",
+                serde_yaml::to_string(article_type)
+                    .unwrap_or_default()
+                    .trim()
+            ),
+        );
+    }
+
+    // Check the slug
+    let valid_slug = SLUG_LENGTH.contains(&slug.len())
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    check(
+        options.slug,
+        !valid_slug,
+        format!(
+            "Invalid slug `{}` (12 to 50 characters of a-z, 0-9, _ and -)",
+            slug
+        ),
+    );
+
+    issues
+}
+
+/// Validates a list of articles and sorts them by the outcome.
+///
+/// The frontmatter is validated as the author wrote it, merged with the `defaults`,
+/// `deep_merge` and `overrides` of the configuration so that a configured default can
+/// satisfy a required key. The `mappings` are not applied, as they rename Zenn's keys
+/// for the site and the rules apply to Zenn's keys. An article that breaks an `error` rule fails, otherwise
+/// an article that breaks a `skip` rule is skipped. Files whose frontmatter cannot
+/// be read or parsed are kept, so that the error is reported when they are converted.
+///
+/// # Arguments
+///
+/// * `files` - The Markdown files of the articles.
+/// * `config` - The configuration, holding the frontmatter to merge and the validation options.
+///
+/// # Returns
+///
+/// * `ValidationReport` - The articles sorted by the outcome of the validation.
+pub fn validate_files(files: Vec<PathBuf>, config: Option<&ConfigFile>) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let default_options = ValidationOptions::default();
+    let options = config
+        .map(|config| &config.validation)
+        .unwrap_or(&default_options);
+
+    for file in files {
+        let Some((_, mut data)) = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| parse_frontmatter(&content).ok())
+        else {
+            report.valid.push(file);
+            continue;
+        };
+
+        // Merge the configured frontmatter, before the keys are renamed by the mappings
+        if let Some(config) = config {
+            merge_frontmatter(&mut data, &config.frontmatter);
+        }
+
+        // Validate the article and apply the strictest policy among its issues
+        let slug = file.file_stem().unwrap_or_default().to_string_lossy();
+        let issues = validate_article(&slug, &data, options);
+        match issues.iter().map(|issue| issue.policy).max() {
+            Some(ValidationPolicy::Error) => {
+                let errors = issues
+                    .into_iter()
+                    .filter(|issue| issue.policy == ValidationPolicy::Error)
+                    .collect();
+                report.failed.push((file, errors));
+            }
+            Some(ValidationPolicy::Skip) => {
+                let issue = issues
+                    .into_iter()
+                    .find(|issue| issue.policy == ValidationPolicy::Skip)
+                    .unwrap();
+                report.skipped.push((file, issue));
+            }
+            _ => {
+                for issue in issues {
+                    report.warnings.push((file.clone(), issue));
+                }
+                report.valid.push(file);
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn frontmatter(yaml: &str) -> Frontmatter {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_article() {
+        let options = ValidationOptions::default();
+
+        let data = frontmatter("title: A\nemoji: '🎉'\ntype: tech\ntopics: [rust]");
+        assert!(validate_article("valid-article-slug", &data, &options).is_empty());
+
+        let data = frontmatter("emoji: '🎉'\ntype: news\ntopics: [a, b, c, d, e, f]");
+        let issues = validate_article("Short", &data, &options);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing required key `title`",
+                "Too many topics (6, at most 5)",
+                "Invalid type news (expected `tech` or `idea`)",
+                "Invalid slug `Short` (12 to 50 characters of a-z, 0-9, _ and -)",
+            ]
+        );
+        assert_eq!(issues[0].policy, ValidationPolicy::Error);
+        assert_eq!(issues[1].policy, ValidationPolicy::Warn);
+    }
+
+    #[test]
+    fn test_validate_article_with_policies() {
+        let options: ValidationOptions =
+            serde_yaml::from_str("required_keys: [title]\nslug: off\ntype: skip").unwrap();
+
+        let data = frontmatter("title: A\ntype: news");
+        let issues = validate_article("x", &data, &options);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].policy, ValidationPolicy::Skip);
+    }
+
+    #[test]
+    fn test_validate_files() {
+        let temp_dir = tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+        let valid = write(
+            "valid-article.md",
+            "---\ntitle: A\nemoji: '🎉'\ntype: tech\ntopics: []\n---\n",
+        );
+        let warned = write(
+            "short.md",
+            "---\ntitle: B\nemoji: '🎉'\ntype: tech\ntopics: []\n---\n",
+        );
+        let failed = write("untitled-article.md", "---\nemoji: '🎉'\n---\n");
+        let skipped = write(
+            "skipped-article.md",
+            "---\ntitle: C\nemoji: '🎉'\ntype: news\ntopics: []\n---\n",
+        );
+
+        let config = ConfigFile {
+            validation: ValidationOptions {
+                article_type: ValidationPolicy::Skip,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let report = validate_files(
+            vec![
+                valid.clone(),
+                warned.clone(),
+                failed.clone(),
+                skipped.clone(),
+            ],
+            Some(&config),
+        );
+
+        assert_eq!(report.valid, vec![valid, warned.clone()]);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].0, warned);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].0, skipped);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, failed);
+        assert_eq!(report.failed[0].1.len(), 3);
+    }
+
+    #[test]
+    fn test_validate_files_with_frontmatter_defaults() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("defaulted-article.md");
+        fs::write(
+            &path,
+            "---
+title: A
+topics: [rust]
+---
+",
+        )
+        .unwrap();
+
+        // The configured defaults satisfy the required keys
+        let config: ConfigFile =
+            serde_yaml::from_str("frontmatter:\n  defaults: { emoji: '🎉', type: tech }").unwrap();
+        let report = validate_files(vec![path.clone()], Some(&config));
+        assert_eq!(report.valid, vec![path.clone()]);
+        assert!(report.warnings.is_empty());
+
+        let report = validate_files(vec![path.clone()], None);
+        assert_eq!(report.failed[0].1.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_files_with_renaming_mappings() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("renamed-article.md");
+        fs::write(
+            &path,
+            "---
+title: A
+emoji: '🎉'
+type: news
+topics: [a, b, c, d, e, f]
+---
+",
+        )
+        .unwrap();
+
+        // The rules apply to Zenn's keys, before they are renamed
+        let config: ConfigFile = serde_yaml::from_str(
+            "frontmatter:\n  mappings: [{ from: emoji, to: icon }, { from: type, to: category }]",
+        )
+        .unwrap();
+        let report = validate_files(vec![path.clone()], Some(&config));
+        assert_eq!(report.valid, vec![path.clone()]);
+        let messages: Vec<&str> = report
+            .warnings
+            .iter()
+            .map(|(_, issue)| issue.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Too many topics (6, at most 5)",
+                "Invalid type news (expected `tech` or `idea`)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::{fs, path::Path};
+
+use similar::TextDiff;
+
+use crate::error::{IoResultExt, Result};
+use crate::report::Action;
+
+/// `OutputMode` controls how the converted files are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputMode {
+    /// Whether to convert the files without writing or deleting anything.
+    pub dry_run: bool,
+    /// Whether to compute a unified diff of each Markdown output against the file on disk.
+    pub diff: bool,
+}
+
+/// `Written` describes the change made, or planned in dry-run mode, to an output file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Written {
+    /// The change to the file.
+    pub action: Action,
+    /// The unified diff of the file, if requested and the file changes.
+    pub diff: Option<String>,
+}
+
+/// Writes the content of an output file, unless it already has this content.
+///
+/// In dry-run mode, nothing is written and the planned change is returned.
+///
+/// # Arguments
+///
+/// * `path` - The path of the output file.
+/// * `content` - The new content of the file.
+/// * `mode` - How the file is written.
+///
+/// # Returns
+///
+/// * `Result<Written>` - The change to the file, or an `Error::Io` if it cannot be written.
+pub fn write_output(path: &Path, content: &str, mode: OutputMode) -> Result<Written> {
+    // Compare the content with the file on disk
+    let existing = fs::read_to_string(path).ok();
+    let action = match &existing {
+        None => Action::Create,
+        Some(existing) if existing == content => Action::Unchanged,
+        Some(_) => Action::Update,
+    };
+    let diff = (mode.diff && action != Action::Unchanged)
+        .then(|| unified_diff(path, existing.as_deref(), content));
+
+    // Write the file, unless it is up to date or in dry-run mode
+    if action != Action::Unchanged && !mode.dry_run {
+        fs::write(path, content).with_path(path)?;
+    }
+
+    Ok(Written { action, diff })
+}
+
+/// Returns the unified diff of a file against its new content.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file, used in the diff header.
+/// * `old` - The content of the file on disk, or `None` if it does not exist.
+/// * `new` - The new content of the file.
+///
+/// # Returns
+///
+/// * `String` - The unified diff, with three lines of context.
+pub fn unified_diff(path: &Path, old: Option<&str>, new: &str) -> String {
+    let name = path.to_string_lossy();
+    let old_name = if old.is_some() { &*name } else { "/dev/null" };
+    TextDiff::from_lines(old.unwrap_or_default(), new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_name, &name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_output() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        let mode = OutputMode {
+            dry_run: false,
+            diff: true,
+        };
+
+        let written = write_output(&path, "line 1\n", mode).unwrap();
+        assert_eq!(written.action, Action::Create);
+        assert!(written.diff.unwrap().starts_with("--- /dev/null\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "line 1\n");
+
+        let written = write_output(&path, "line 1\n", mode).unwrap();
+        assert_eq!(written.action, Action::Unchanged);
+        assert_eq!(written.diff, None);
+    }
+
+    #[test]
+    fn test_write_output_dry_run() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "title\nold\n").unwrap();
+        let mode = OutputMode {
+            dry_run: true,
+            diff: true,
+        };
+
+        // The planned change is returned, but the file is left alone
+        let written = write_output(&path, "title\nnew\n", mode).unwrap();
+        assert_eq!(written.action, Action::Update);
+        assert!(written.diff.unwrap().ends_with(" title\n-old\n+new\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "title\nold\n");
+    }
+}
@@ -0,0 +1,455 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::error::{Error, Result};
+
+/// The outcome of converting or copying a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    /// The file was converted and written to its destination.
+    Converted,
+    /// The destination already had the same content and was not written.
+    Unchanged,
+    /// The file was left out, e.g. by the publication policy or a validation rule.
+    Skipped,
+    /// The file could not be converted.
+    Failed,
+    /// The output of a removed source was deleted.
+    Deleted,
+}
+
+impl fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            FileStatus::Converted => "converted",
+            FileStatus::Unchanged => "unchanged",
+            FileStatus::Skipped => "skipped",
+            FileStatus::Failed => "failed",
+            FileStatus::Deleted => "deleted",
+        };
+        f.pad(status)
+    }
+}
+
+/// The change made to the destination of a file, or planned in dry-run mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// The destination did not exist.
+    Create,
+    /// The destination had a different content.
+    Update,
+    /// The destination already had the same content.
+    Unchanged,
+    /// The destination was deleted.
+    Delete,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let action = match self {
+            Action::Create => "create",
+            Action::Update => "update",
+            Action::Unchanged => "unchanged",
+            Action::Delete => "delete",
+        };
+        f.pad(action)
+    }
+}
+
+/// The kind of content a file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileKind {
+    /// A Zenn article.
+    Article,
+    /// A Zenn book, reported as a whole.
+    Book,
+    /// An image.
+    Image,
+}
+
+impl fmt::Display for FileKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            FileKind::Article => "article",
+            FileKind::Book => "book",
+            FileKind::Image => "image",
+        };
+        f.pad(kind)
+    }
+}
+
+/// `FileReport` is the entry of a single file in a [`ConversionReport`].
+#[derive(Debug, Serialize)]
+pub struct FileReport {
+    /// The kind of content.
+    pub kind: FileKind,
+    /// The outcome of the conversion.
+    pub status: FileStatus,
+    /// The source file (or directory, for books).
+    pub source: PathBuf,
+    /// The destination file (or directory, for books), if the file was not left out.
+    pub destination: Option<PathBuf>,
+    /// The change made to the destination, if it was written, found up to date or deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+    /// The warnings raised while converting the file.
+    pub warnings: Vec<String>,
+    /// The reason the file was skipped, or the error it failed with.
+    pub message: Option<String>,
+    /// The number of bytes written, for copied images.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    /// The unified diff of the Markdown outputs against the files on disk, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// The time spent on the file.
+    #[serde(rename = "duration_ms", serialize_with = "serialize_millis")]
+    pub duration: Duration,
+    /// The error the file failed with, if any.
+    #[serde(skip)]
+    pub error: Option<Error>,
+}
+
+impl FileReport {
+    /// Creates an entry with the given status and no destination, warnings or timing.
+    pub fn new(kind: FileKind, status: FileStatus, source: impl Into<PathBuf>) -> Self {
+        FileReport {
+            kind,
+            status,
+            source: source.into(),
+            destination: None,
+            action: None,
+            warnings: Vec::new(),
+            message: None,
+            bytes: None,
+            diff: None,
+            duration: Duration::ZERO,
+            error: None,
+        }
+    }
+
+    /// Creates an entry for a file left out for the given reason.
+    pub fn skipped(kind: FileKind, source: impl Into<PathBuf>, reason: impl fmt::Display) -> Self {
+        FileReport {
+            message: Some(reason.to_string()),
+            ..FileReport::new(kind, FileStatus::Skipped, source)
+        }
+    }
+
+    /// Creates an entry for a file that failed with the given error.
+    pub fn failed(kind: FileKind, source: impl Into<PathBuf>, error: Error) -> Self {
+        FileReport {
+            message: Some(error.to_string()),
+            error: Some(error),
+            ..FileReport::new(kind, FileStatus::Failed, source)
+        }
+    }
+
+    /// Sets the time spent on the file from the instant its conversion started.
+    pub fn timed(mut self, started: Instant) -> Self {
+        self.duration = started.elapsed();
+        self
+    }
+}
+
+/// `ReportSummary` counts the entries of a [`ConversionReport`] by status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReportSummary {
+    /// The number of converted files.
+    pub converted: usize,
+    /// The number of files whose destination was already up to date.
+    pub unchanged: usize,
+    /// The number of skipped files.
+    pub skipped: usize,
+    /// The number of failed files.
+    pub failed: usize,
+    /// The number of deleted outputs.
+    pub deleted: usize,
+    /// The number of bytes of images copied.
+    pub bytes: u64,
+}
+
+/// `ConversionReport` lists the outcome of every file processed by a conversion.
+///
+/// A conversion that runs to the end returns its report even if some files failed,
+/// e.g. in keep-going mode or because of a validation rule whose policy is `error`.
+/// Use [`ConversionReport::has_failures`] or [`ConversionReport::into_result`] to
+/// check for them.
+#[derive(Debug, Default, Serialize)]
+pub struct ConversionReport {
+    /// The entries of the processed files, in processing order.
+    pub files: Vec<FileReport>,
+    /// The total time spent on the conversion.
+    #[serde(rename = "duration_ms", serialize_with = "serialize_millis")]
+    pub duration: Duration,
+    /// Whether nothing was written, the entries describing the planned changes.
+    pub dry_run: bool,
+}
+
+impl ConversionReport {
+    /// Adds the entry of a file.
+    pub fn push(&mut self, file: FileReport) {
+        self.files.push(file);
+    }
+
+    /// Appends the entries of another report, e.g. to combine the steps of a run.
+    pub fn extend(&mut self, other: ConversionReport) {
+        self.files.extend(other.files);
+        self.duration += other.duration;
+        self.dry_run |= other.dry_run;
+    }
+
+    /// Sets the total time spent on the conversion from the instant it started.
+    pub fn timed(mut self, started: Instant) -> Self {
+        self.duration = started.elapsed();
+        self
+    }
+
+    /// Counts the entries by status.
+    pub fn summary(&self) -> ReportSummary {
+        let mut summary = ReportSummary::default();
+        for file in &self.files {
+            match file.status {
+                FileStatus::Converted => summary.converted += 1,
+                FileStatus::Unchanged => summary.unchanged += 1,
+                FileStatus::Skipped => summary.skipped += 1,
+                FileStatus::Failed => summary.failed += 1,
+                FileStatus::Deleted => summary.deleted += 1,
+            }
+            summary.bytes += file.bytes.unwrap_or_default();
+        }
+        summary
+    }
+
+    /// Returns the entries with the given status.
+    pub fn with_status(&self, status: FileStatus) -> impl Iterator<Item = &FileReport> {
+        self.files.iter().filter(move |file| file.status == status)
+    }
+
+    /// Returns whether any file failed.
+    pub fn has_failures(&self) -> bool {
+        self.with_status(FileStatus::Failed).next().is_some()
+    }
+
+    /// Returns whether the given file was converted or already up to date.
+    pub fn is_written(&self, source: &Path) -> bool {
+        self.files.iter().any(|file| {
+            file.source == source
+                && matches!(file.status, FileStatus::Converted | FileStatus::Unchanged)
+        })
+    }
+
+    /// Converts the report into an error if any file failed.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The report if no file failed. Otherwise an
+    ///   [`Error::Validation`] if only validation rules failed, or an [`Error::Files`]
+    ///   listing every failed file with its error.
+    pub fn into_result(mut self) -> Result<Self> {
+        let mut failures: Vec<(PathBuf, Error)> = self
+            .files
+            .iter_mut()
+            .filter_map(|file| Some((file.source.clone(), file.error.take()?)))
+            .collect();
+        if failures.is_empty() {
+            return Ok(self);
+        }
+
+        // Merge the validation failures when nothing else failed
+        if failures
+            .iter()
+            .all(|(_, error)| matches!(error, Error::Validation { .. }))
+        {
+            let failures = failures
+                .into_iter()
+                .flat_map(|(_, error)| match error {
+                    Error::Validation { failures } => failures,
+                    _ => Vec::new(),
+                })
+                .collect();
+            return Err(Error::Validation { failures });
+        }
+
+        failures.sort_by_key(|(_, error)| matches!(error, Error::Validation { .. }));
+        Err(Error::Files { failures })
+    }
+
+    /// Serializes the report, with its summary, into pretty-printed JSON.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String>` - The JSON text, or an `Error::Transform` if it cannot be serialized.
+    pub fn to_json(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct JsonReport<'a> {
+            summary: ReportSummary,
+            #[serde(flatten)]
+            report: &'a ConversionReport,
+        }
+
+        let report = JsonReport {
+            summary: self.summary(),
+            report: self,
+        };
+        serde_json::to_string_pretty(&report).map_err(|e| Error::transform("", e))
+    }
+}
+
+impl fmt::Display for ConversionReport {
+    /// Formats the report as text, with a summary line followed by a line per file.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = self.summary();
+        if self.dry_run {
+            write!(f, "Dry run: ")?;
+        }
+        write!(
+            f,
+            "{} converted, {} unchanged, {} skipped, {} failed, {} deleted, {} bytes of images copied in {} ms",
+            summary.converted,
+            summary.unchanged,
+            summary.skipped,
+            summary.failed,
+            summary.deleted,
+            summary.bytes,
+            self.duration.as_millis()
+        )?;
+
+        for file in &self.files {
+            write!(f, "\n{:<9} {:<7} {:?}", file.status, file.kind, file.source)?;
+            if let Some(destination) = &file.destination {
+                write!(f, " -> {:?}", destination)?;
+            }
+            write!(f, " ({} ms)", file.duration.as_millis())?;
+            if let Some(message) = &file.message {
+                write!(f, "\n\t{}", message.replace('\n', "\n\t"))?;
+            }
+            for warning in &file.warnings {
+                write!(f, "\n\twarning: {}", warning)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serializes a duration as a whole number of milliseconds.
+fn serialize_millis<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::validation::{ValidationIssue, ValidationPolicy};
+
+    fn validation_error(file: &str) -> Error {
+        Error::Validation {
+            failures: vec![(
+                PathBuf::from(file),
+                vec![ValidationIssue {
+                    policy: ValidationPolicy::Error,
+                    message: "Missing required key `title`".to_string(),
+                }],
+            )],
+        }
+    }
+
+    fn report() -> ConversionReport {
+        let mut converted = FileReport::new(FileKind::Article, FileStatus::Converted, "src/a.md");
+        converted.destination = Some(PathBuf::from("dest/a.md"));
+        converted.warnings.push("Unknown embed".to_string());
+        let mut image = FileReport::new(FileKind::Image, FileStatus::Converted, "src/a.png");
+        image.bytes = Some(42);
+
+        ConversionReport {
+            files: vec![
+                converted,
+                image,
+                FileReport::skipped(FileKind::Article, "src/b.md", "unpublished"),
+                FileReport::failed(FileKind::Article, "src/c.md", validation_error("src/c.md")),
+            ],
+            duration: Duration::from_millis(5),
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let report = report();
+
+        assert_eq!(
+            report.summary(),
+            ReportSummary {
+                converted: 2,
+                unchanged: 0,
+                skipped: 1,
+                failed: 1,
+                deleted: 0,
+                bytes: 42,
+            }
+        );
+        assert!(report.has_failures());
+        assert!(report.is_written(Path::new("src/a.md")));
+        assert!(!report.is_written(Path::new("src/b.md")));
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let report = report();
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["summary"]["converted"], 2);
+        assert_eq!(json["duration_ms"], 5);
+        assert_eq!(json["files"][0]["status"], "converted");
+        assert_eq!(json["files"][0]["destination"], "dest/a.md");
+        assert_eq!(json["files"][0]["warnings"][0], "Unknown embed");
+        assert_eq!(json["files"][1]["bytes"], 42);
+        assert_eq!(json["files"][2]["message"], "unpublished");
+
+        let text = report.to_string();
+        assert!(text.starts_with(
+            "2 converted, 0 unchanged, 1 skipped, 1 failed, 0 deleted, 42 bytes of images copied in 5 ms\n"
+        ));
+        assert!(text.contains(
+            "converted article \"src/a.md\" -> \"dest/a.md\" (0 ms)\n\twarning: Unknown embed"
+        ));
+        assert!(text.contains("skipped   article \"src/b.md\" (0 ms)\n\tunpublished"));
+    }
+
+    #[test]
+    fn test_report_into_result() {
+        assert!(ConversionReport::default().into_result().is_ok());
+
+        // Validation failures alone are merged into a single error
+        assert!(matches!(
+            report().into_result(),
+            Err(Error::Validation { failures }) if failures.len() == 1
+        ));
+
+        // Other failures are listed first, along with the validation failures
+        let mut report = report();
+        report.push(FileReport::failed(
+            FileKind::Image,
+            "src/d.png",
+            Error::transform("src/d.png", "unreadable"),
+        ));
+        match report.into_result() {
+            Err(Error::Files { failures }) => {
+                assert_eq!(failures.len(), 2);
+                assert_eq!(failures[0].0, PathBuf::from("src/d.png"));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
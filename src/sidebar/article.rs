@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use super::SidebarItem;
+use crate::error::{IoResultExt, Result};
+use crate::markdown::{frontmatter::parse_frontmatter, publication::parse_datetime};
+
+/// The text of the last group, listing the articles that have no key to be grouped by.
+const UNGROUPED_TEXT: &str = "Other";
+
+/// How articles are grouped in the generated sidebar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SidebarGroupBy {
+    /// List the articles without groups.
+    #[default]
+    None,
+    /// Group the articles by their Zenn `type` (`tech` / `idea`).
+    /// Articles without a `type` are listed in a last `Other` group.
+    Type,
+    /// Group the articles by their Zenn `topics`. An article appears in each of its topics,
+    /// and articles without topics are listed in a last `Other` group.
+    Topic,
+}
+
+/// How articles are sorted in the generated sidebar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SidebarSortBy {
+    /// Sort the articles by title.
+    #[default]
+    Title,
+    /// Sort the articles by `published_at`, newest first.
+    PublishedAt,
+}
+
+/// `SidebarOptions` controls the sidebar file generated from the converted articles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SidebarOptions {
+    /// The path of the sidebar file. A `.json` file holds a JSON array, while
+    /// a `.mts`, `.ts` or `.js` file holds a module exporting the array.
+    pub path: String,
+    /// How the articles are grouped.
+    #[serde(default)]
+    pub group_by: SidebarGroupBy,
+    /// How the articles are sorted.
+    #[serde(default)]
+    pub sort_by: SidebarSortBy,
+}
+
+/// An article listed in the sidebar.
+#[derive(Debug, Clone, PartialEq)]
+struct ArticleEntry {
+    title: String,
+    link: String,
+    emoji: Option<String>,
+    article_type: Option<String>,
+    topics: Vec<String>,
+    published_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Builds the sidebar items for the given articles.
+///
+/// Articles without a `title` in their frontmatter are not listed.
+///
+/// # Arguments
+///
+/// * `files` - The Markdown files of the articles.
+/// * `articles_url` - The URL path where the articles are served (e.g. `/articles`).
+/// * `options` - The sidebar options.
+///
+/// # Returns
+///
+/// * `Result<Vec<SidebarItem>>` - The sidebar items, or an error naming the article that could not be read.
+pub fn build_article_sidebar(
+    files: &[PathBuf],
+    articles_url: &str,
+    options: &SidebarOptions,
+) -> Result<Vec<SidebarItem>> {
+    // Read the frontmatter of every article
+    let mut entries = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(file).with_path(file)?;
+        let (_, data) = parse_frontmatter(&content).map_err(|e| e.with_path(file))?;
+        let Some(title) = data.get("title").and_then(Value::as_str) else {
+            continue;
+        };
+
+        let slug = file.file_stem().unwrap().to_string_lossy();
+        entries.push(ArticleEntry {
+            title: title.to_string(),
+            link: format!("{}/{}", articles_url.trim_end_matches('/'), slug),
+            emoji: string_value(data.get("emoji")),
+            article_type: string_value(data.get("type")),
+            topics: data
+                .get("topics")
+                .and_then(Value::as_sequence)
+                .map(|topics| {
+                    topics
+                        .iter()
+                        .filter_map(|t| string_value(Some(t)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            published_at: string_value(data.get("published_at"))
+                .and_then(|published_at| parse_datetime(&published_at)),
+        });
+    }
+
+    // Sort the articles
+    match options.sort_by {
+        SidebarSortBy::Title => entries.sort_by(|a, b| a.title.cmp(&b.title)),
+        SidebarSortBy::PublishedAt => entries.sort_by(|a, b| {
+            // Newest first, with unscheduled or unparseable dates last
+            match (&a.published_at, &b.published_at) {
+                (Some(a), Some(b)) => b.cmp(a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        }),
+    }
+
+    // Group the articles
+    let items = match options.group_by {
+        SidebarGroupBy::None => entries.iter().map(render_entry).collect(),
+        SidebarGroupBy::Type => group_entries(&entries, |entry| {
+            entry.article_type.clone().into_iter().collect()
+        }),
+        SidebarGroupBy::Topic => group_entries(&entries, |entry| entry.topics.clone()),
+    };
+
+    Ok(items)
+}
+
+/// Groups the entries by the keys returned by `keys`, keeping their order within each group.
+///
+/// Entries without any key are listed in a last group, so that no article is left out.
+fn group_entries(
+    entries: &[ArticleEntry],
+    keys: impl Fn(&ArticleEntry) -> Vec<String>,
+) -> Vec<SidebarItem> {
+    let mut groups: BTreeMap<String, Vec<SidebarItem>> = BTreeMap::new();
+    let mut ungrouped = Vec::new();
+    for entry in entries {
+        let entry_keys = keys(entry);
+        if entry_keys.is_empty() {
+            ungrouped.push(render_entry(entry));
+        }
+        for key in entry_keys {
+            groups.entry(key).or_default().push(render_entry(entry));
+        }
+    }
+
+    let mut items: Vec<SidebarItem> = groups
+        .into_iter()
+        .map(|(key, items)| SidebarItem::group(&key, items))
+        .collect();
+    if !ungrouped.is_empty() {
+        items.push(SidebarItem::group(UNGROUPED_TEXT, ungrouped));
+    }
+    items
+}
+
+/// Renders an article as a sidebar item, prefixing its title with its emoji.
+fn render_entry(entry: &ArticleEntry) -> SidebarItem {
+    let text = match &entry.emoji {
+        Some(emoji) => format!("{} {}", emoji, entry.title),
+        None => entry.title.clone(),
+    };
+
+    let mut item = SidebarItem::page(&text, &entry.link);
+    item.emoji = entry.emoji.clone();
+    item
+}
+
+/// Converts a scalar YAML value into a string.
+fn string_value(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_articles(dir: &std::path::Path) -> Vec<PathBuf> {
+        let articles = [
+            (
+                "b.md",
+                "---\ntitle: B\nemoji: '🎉'\ntype: tech\ntopics: [rust, vitepress]\npublished_at: 2024-08-01 09:00\n---\n",
+            ),
+            (
+                "a.md",
+                "---\ntitle: A\nemoji: '📸'\ntype: idea\ntopics: [rust]\npublished_at: 2024-08-02 09:00\n---\n",
+            ),
+            ("c.md", "---\ntitle: C\ntype: tech\n---\n"),
+            (
+                "d.md",
+                "---\ntitle: D\ntype: idea\npublished_at: 2024-08-02T08:00:00+09:00\n---\n",
+            ),
+            ("untitled.md", "---\nemoji: '🔑'\n---\n"),
+        ];
+
+        articles
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                fs::write(&path, content).unwrap();
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_article_sidebar() {
+        let temp_dir = tempdir().unwrap();
+        let files = write_articles(temp_dir.path());
+
+        let items = build_article_sidebar(&files, "/articles", &SidebarOptions::default()).unwrap();
+
+        let texts: Vec<&str> = items.iter().map(|item| item.text.as_str()).collect();
+        assert_eq!(texts, vec!["📸 A", "🎉 B", "C", "D"]);
+        assert_eq!(items[0].link.as_deref(), Some("/articles/a"));
+        assert_eq!(items[0].emoji.as_deref(), Some("📸"));
+    }
+
+    #[test]
+    fn test_build_article_sidebar_grouped_by_type() {
+        let temp_dir = tempdir().unwrap();
+        let files = write_articles(temp_dir.path());
+
+        let untyped = temp_dir.path().join("e.md");
+        fs::write(&untyped, "---\ntitle: E\n---\n").unwrap();
+        let files = [files, vec![untyped]].concat();
+
+        let options = SidebarOptions {
+            group_by: SidebarGroupBy::Type,
+            sort_by: SidebarSortBy::PublishedAt,
+            ..Default::default()
+        };
+        let items = build_article_sidebar(&files, "/articles/", &options).unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].text, "idea");
+        assert_eq!(items[1].text, "tech");
+
+        // Articles without a type are listed last
+        assert_eq!(items[2].text, "Other");
+        assert_eq!(items[2].items[0].text, "E");
+
+        // The dates are compared as dates, not as strings
+        let idea: Vec<&str> = items[0].items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(idea, vec!["📸 A", "D"]);
+        let tech: Vec<&str> = items[1].items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(tech, vec!["🎉 B", "C"]);
+    }
+
+    #[test]
+    fn test_sidebar_options_require_path() {
+        let error = serde_yaml::from_str::<SidebarOptions>("group_by: type").unwrap_err();
+        assert!(error.to_string().contains("missing field `path`"));
+
+        let options: SidebarOptions = serde_yaml::from_str("path: sidebar.json").unwrap();
+        assert_eq!(options.sort_by, SidebarSortBy::Title);
+    }
+
+    #[test]
+    fn test_build_article_sidebar_grouped_by_topic() {
+        let temp_dir = tempdir().unwrap();
+        let files = write_articles(temp_dir.path());
+
+        let options = SidebarOptions {
+            group_by: SidebarGroupBy::Topic,
+            sort_by: SidebarSortBy::PublishedAt,
+            ..Default::default()
+        };
+        let items = build_article_sidebar(&files, "/articles", &options).unwrap();
+
+        let rust: Vec<&str> = items[0].items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(items[0].text, "rust");
+        assert_eq!(rust, vec!["📸 A", "🎉 B"]);
+        assert_eq!(items[1].text, "vitepress");
+        assert_eq!(items[1].items.len(), 1);
+
+        // Articles without topics are listed last
+        let other: Vec<&str> = items[2].items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(items[2].text, "Other");
+        assert_eq!(other, vec!["D", "C"]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, IoResultExt, Result};
+
+pub mod article;
+
+/// `SidebarItem` is a VitePress sidebar entry.
+///
+/// It serializes to the shape expected by `themeConfig.sidebar`, so a generated
+/// file can be imported from `.vitepress/config.mts` as-is.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidebarItem {
+    /// The text shown in the sidebar.
+    pub text: String,
+
+    /// The link of the page, if the item is a page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+
+    /// The emoji of the article, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+
+    /// Whether the group is collapsed, if the item is a group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<bool>,
+
+    /// The child items, if the item is a group.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub items: Vec<SidebarItem>,
+}
+
+impl SidebarItem {
+    /// Creates a sidebar item linking to a page.
+    pub fn page(text: &str, link: &str) -> Self {
+        SidebarItem {
+            text: text.to_string(),
+            link: Some(link.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a sidebar group containing the given items.
+    pub fn group(text: &str, items: Vec<SidebarItem>) -> Self {
+        SidebarItem {
+            text: text.to_string(),
+            collapsed: Some(false),
+            items,
+            ..Default::default()
+        }
+    }
+}
+
+/// Writes sidebar items to a file, choosing the format from its extension.
+///
+/// A `.mts`, `.ts` or `.js` file is written as a module with a default export,
+/// while any other file is written as JSON.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file to write.
+/// * `items` - The sidebar items to write.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns `Ok(())` on success, or an [`Error`](crate::Error) naming the file on failure.
+pub fn write_sidebar(path: &Path, items: &[SidebarItem]) -> Result<()> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("mts" | "ts" | "js" | "mjs") => write_sidebar_module(path, items),
+        _ => write_sidebar_json(path, items),
+    }
+}
+
+/// Writes sidebar items to a JSON file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the JSON file to write.
+/// * `items` - The sidebar items to write.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns `Ok(())` on success, or an [`Error`](crate::Error) naming the file on failure.
+pub fn write_sidebar_json(path: &Path, items: &[SidebarItem]) -> Result<()> {
+    let json = serde_json::to_string_pretty(items).map_err(|e| Error::transform(path, e))?;
+    fs::write(path, format!("{}\n", json)).with_path(path)?;
+
+    Ok(())
+}
+
+/// Writes sidebar items to a JavaScript/TypeScript module with a default export.
+///
+/// # Arguments
+///
+/// * `path` - The path of the module to write.
+/// * `items` - The sidebar items to write.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns `Ok(())` on success, or an [`Error`](crate::Error) naming the file on failure.
+pub fn write_sidebar_module(path: &Path, items: &[SidebarItem]) -> Result<()> {
+    let json = serde_json::to_string_pretty(items).map_err(|e| Error::transform(path, e))?;
+    fs::write(
+        path,
+        format!(
+            "// This file is generated by zenn2press. Do not edit it manually.\nexport default {}\n",
+            json
+        ),
+    )
+    .with_path(path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_sidebar_json() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("sidebar.json");
+
+        let items = vec![SidebarItem::group(
+            "Book",
+            vec![SidebarItem::page("Intro", "/books/book/intro")],
+        )];
+        write_sidebar_json(&path, &items).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "text": "Book",
+                "collapsed": false,
+                "items": [{ "text": "Intro", "link": "/books/book/intro" }]
+            }])
+        );
+    }
+
+    #[test]
+    fn test_write_sidebar_module() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("sidebar.mts");
+
+        let items = vec![SidebarItem::page("Intro", "/intro")];
+        write_sidebar(&path, &items).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("export default ["));
+        assert!(content.contains("\"link\": \"/intro\""));
+    }
+}
@@ -1,2 +1,39 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the progress of the application is printed to the standard error,
+/// keeping the standard output for the report.
+static PROGRESS_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the progress of the application is printed to the standard error.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the report is printed to the standard output.
+pub fn set_progress_to_stderr(enabled: bool) {
+    PROGRESS_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the progress of the application is printed to the standard error.
+pub fn progress_to_stderr() -> bool {
+    PROGRESS_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// Prints a line of progress to the standard output, or to the standard error
+/// when the standard output is kept for the report.
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if $crate::utils::progress_to_stderr() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+pub mod convert;
 pub mod figlet;
+pub mod init;
+pub mod list;
 pub mod property;
+pub mod report;
+pub mod watch;
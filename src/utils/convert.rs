@@ -0,0 +1,231 @@
+use std::{env, time::Instant};
+
+use colored::Colorize;
+use indicatif::HumanDuration;
+use zenn2press::{
+    converter::{ARTICLES_DIR, BOOKS_DIR, IMAGES_DIR},
+    ConversionReport, Converter, ConverterBuilder,
+};
+
+use super::{
+    property::{print_configuration_details, Properties},
+    report::{
+        print_check, print_deletions, print_diffs, print_failures, print_plan, print_skipped,
+        print_warnings, write_report,
+    },
+};
+
+/// Converts the Zenn repository, printing the progress of each step.
+///
+/// # Arguments
+///
+/// * `properties` - The properties derived from the command-line arguments.
+/// * `started` - The start time of the process, used for the duration of the summary and the report.
+///
+/// # Returns
+///
+/// * `bool` - Whether any step failed.
+pub async fn convert(properties: &Properties, started: Instant) -> bool {
+    // Print configuration details for debugging
+    print_configuration_details(properties);
+
+    // Run every step of the conversion
+    let converter = build(converter_builder(properties));
+    let (mut report, mut failed) = run_steps(&converter, properties).await;
+
+    // List the planned changes in dry-run mode, or the outputs deleted by `--prune`
+    if properties.dry_run {
+        print_plan(&report);
+    } else {
+        print_deletions(&report);
+    }
+    print_diffs(&report);
+
+    // List the files that failed in keep-going mode or validation, before the summary
+    print_failures(&report);
+    failed |= report.has_failures();
+    outln!("\n\n✨ Done in {}", HumanDuration(started.elapsed()));
+
+    failed | output_report(&mut report, properties, started)
+}
+
+/// Checks the Zenn repository by converting every file in dry-run mode, and summarizes the problems found.
+///
+/// The destination directories are optional: those that are not set are replaced with
+/// empty directories, as nothing is written in dry-run mode.
+///
+/// # Arguments
+///
+/// * `properties` - The properties derived from the command-line arguments.
+/// * `started` - The start time of the process, used for the duration of the summary and the report.
+///
+/// # Returns
+///
+/// * `bool` - Whether any file failed the check.
+pub async fn check(mut properties: Properties, started: Instant) -> bool {
+    // Print configuration details for debugging
+    print_configuration_details(&properties);
+
+    // Check against empty destination directories when they are not set
+    let scratch_dir = env::temp_dir().join("zenn2press-check");
+    let scratch = |name: &str| Some(scratch_dir.join(name).to_string_lossy().to_string());
+    properties.dest_dir = properties.dest_dir.or_else(|| scratch(ARTICLES_DIR));
+    properties.dest_images_dir = properties.dest_images_dir.or_else(|| scratch(IMAGES_DIR));
+    properties.dest_books_dir = properties.dest_books_dir.or_else(|| scratch(BOOKS_DIR));
+
+    // Run every step of the conversion, without writing anything
+    let converter = build(converter_builder(&properties));
+    let (mut report, mut failed) = run_steps(&converter, &properties).await;
+    print_diffs(&report);
+
+    // List the files that failed, and summarize the problems found
+    print_failures(&report);
+    print_check(&report);
+    failed |= report.has_failures();
+    outln!("\n\n✨ Done in {}", HumanDuration(started.elapsed()));
+
+    failed | output_report(&mut report, &properties, started)
+}
+
+/// Returns the builder of the converter described by the properties.
+///
+/// # Arguments
+///
+/// * `properties` - The properties derived from the command-line arguments.
+///
+/// # Returns
+///
+/// * `ConverterBuilder` - The builder, with the directories, filters and options set.
+pub fn converter_builder(properties: &Properties) -> ConverterBuilder {
+    let mut builder = Converter::builder()
+        .src_dir(&properties.src_dir)
+        .include(&properties.include)
+        .exclude(&properties.exclude)
+        .include_books(&properties.include_books)
+        .exclude_books(&properties.exclude_books)
+        .keep_going(properties.keep_going)
+        .prune(properties.prune)
+        .dry_run(properties.dry_run)
+        .diff(properties.diff);
+    if let Some(config_file) = &properties.config_file {
+        builder = builder.config_file(config_file);
+    }
+    if let Some(dest_dir) = &properties.dest_dir {
+        builder = builder.dest_dir(dest_dir);
+    }
+    if let Some(dest_images_dir) = &properties.dest_images_dir {
+        builder = builder.dest_images_dir(dest_images_dir);
+    }
+    if let Some(dest_books_dir) = &properties.dest_books_dir {
+        builder = builder.dest_books_dir(dest_books_dir);
+    }
+    builder
+}
+
+/// Builds the converter, exiting with an error if the configuration cannot be read.
+fn build(builder: ConverterBuilder) -> Converter {
+    builder.build().unwrap_or_else(|e| {
+        eprintln!("Error reading the configuration: {}", e);
+        std::process::exit(1);
+    })
+}
+
+/// Converts the articles, books and images, printing the progress and warnings of each step.
+///
+/// # Arguments
+///
+/// * `converter` - The converter to run.
+/// * `properties` - The properties derived from the command-line arguments.
+///
+/// # Returns
+///
+/// * `(ConversionReport, bool)` - The report of every step, and whether any step failed.
+async fn run_steps(converter: &Converter, properties: &Properties) -> (ConversionReport, bool) {
+    // Whether any step failed, to exit with a non-zero status
+    let mut failed = false;
+
+    // The report of every step
+    let mut report = ConversionReport::default();
+
+    // Print status update for copying markdown files
+    outln!(
+        "{} 📚 {}",
+        "[2/4]".bright_black().bold(),
+        "Copy markdown files:".bold().underline()
+    );
+
+    // Copy markdown files from srcDir to destDir
+    // If an error occurs, print the error message
+    match converter.convert_articles() {
+        Ok(articles) => {
+            print_warnings(&articles);
+            print_skipped(&articles);
+            report.extend(articles)
+        }
+        Err(e) => {
+            eprintln!("Error copying markdown files: {}", e);
+            failed = true;
+        }
+    }
+
+    // Print status update for converting books
+    outln!(
+        "{} 📖 {}",
+        "[3/4]".bright_black().bold(),
+        "Convert books:".bold().underline()
+    );
+
+    // Convert books from srcDir to destBooksDir, if the destination is provided
+    match properties.dest_books_dir.as_deref() {
+        Some(_) => match converter.convert_books() {
+            Ok(books) => {
+                print_warnings(&books);
+                print_skipped(&books);
+                report.extend(books)
+            }
+            Err(e) => {
+                eprintln!("Error converting books: {}", e);
+                failed = true;
+            }
+        },
+        None => outln!("Skipped (no books destination directory)."),
+    }
+
+    // Print status update for copying image files
+    outln!(
+        "{} 🖼️ {}",
+        "[4/4]".bright_black().bold(),
+        "Copy image files:".bold().underline()
+    );
+
+    // Copy images from srcDir to destDir
+    // Await the result and handle success or error
+    match converter.convert_images().await {
+        Ok(images) => {
+            print_warnings(&images);
+            report.extend(images)
+        }
+        Err(e) => {
+            eprintln!("Error copying images: {}", e);
+            failed = true;
+        }
+    }
+
+    (report, failed)
+}
+
+/// Prints or writes the report, if requested.
+///
+/// # Returns
+///
+/// * `bool` - Whether the report could not be written.
+fn output_report(report: &mut ConversionReport, properties: &Properties, started: Instant) -> bool {
+    let Some(format) = properties.report else {
+        return false;
+    };
+
+    report.duration = started.elapsed();
+    write_report(report, format, properties.report_file.as_deref())
+        .map_err(|e| eprintln!("Error writing the report: {}", e))
+        .is_err()
+}
@@ -14,8 +14,8 @@ pub fn print_figlet() {
     let colored_figure = figure.unwrap().to_string().bright_blue().bold();
 
     // Print the colored FIGlet text
-    println!("{}", colored_figure);
+    outln!("{}", colored_figure);
 
     // Print the description
-    println!("== {} ==\n", DESCRIPTION.bold())
+    outln!("== {} ==\n", DESCRIPTION.bold())
 }
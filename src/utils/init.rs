@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use colored::Colorize;
+use zenn2press::markdown::config::write_config_template;
+
+use super::property::InitProperties;
+
+/// Writes a starter configuration file and prints how to use it.
+///
+/// # Arguments
+///
+/// * `properties` - The properties of the `init` subcommand.
+///
+/// # Returns
+///
+/// * `bool` - Whether the file could not be written.
+pub fn init(properties: InitProperties) -> bool {
+    let config_file = &properties.config_file;
+
+    // Refuse to replace an existing file, unless `--force` is given
+    if !properties.force && Path::new(config_file).exists() {
+        eprintln!(
+            "Error: {:?} already exists (use --force to replace it)",
+            config_file
+        );
+        return true;
+    }
+
+    match write_config_template(
+        config_file,
+        &properties.paths,
+        &properties.filters,
+        properties.force,
+    ) {
+        Ok(()) => {
+            println!("✨ {} {:?}", "Created".green().bold(), config_file);
+            println!("Edit it, then run: zenn2press convert -c {}", config_file);
+            false
+        }
+        Err(e) => {
+            eprintln!("Error writing the configuration file: {}", e);
+            true
+        }
+    }
+}
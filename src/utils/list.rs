@@ -0,0 +1,45 @@
+use zenn2press::Converter;
+
+use super::property::{ListProperties, ReportFormat};
+
+/// Prints the articles, books and images of the Zenn repository with their metadata.
+///
+/// # Arguments
+///
+/// * `properties` - The properties of the `list` subcommand.
+///
+/// # Returns
+///
+/// * `bool` - Whether the content could not be listed.
+pub async fn list(properties: ListProperties) -> bool {
+    // Build the converter from the properties
+    let mut builder = Converter::builder()
+        .src_dir(&properties.src_dir)
+        .include(&properties.include)
+        .exclude(&properties.exclude)
+        .include_books(&properties.include_books)
+        .exclude_books(&properties.exclude_books);
+    if let Some(config_file) = &properties.config_file {
+        builder = builder.config_file(config_file);
+    }
+
+    // List the content and print it in the requested format
+    let listed = match builder.build() {
+        Ok(converter) => converter.list().await,
+        Err(e) => Err(e),
+    };
+    let output = listed.and_then(|inventory| match properties.format {
+        ReportFormat::Json => inventory.to_json(),
+        ReportFormat::Text => Ok(inventory.to_string()),
+    });
+    match output {
+        Ok(output) => {
+            println!("{}", output);
+            false
+        }
+        Err(e) => {
+            eprintln!("Error listing the Zenn content: {}", e);
+            true
+        }
+    }
+}
@@ -1,74 +1,323 @@
-use args::Args;
-use clap::Parser;
+pub use args::ReportFormat;
+
+use args::{Args, Command, CommonArgs, ReportArgs};
+use clap::{error::ErrorKind, CommandFactory, Parser};
 use colored::Colorize;
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
 
-use crate::constants::{ARTICLES_DIR, IMAGES_DIR};
+use zenn2press::converter::{ARTICLES_DIR, BOOKS_DIR, IMAGES_DIR};
+use zenn2press::markdown::config::{
+    read_config_file, BookFiltersConfig, ConfigFile, FiltersConfig, PathsConfig,
+};
 
 mod args;
 
+/// The configuration file written by `init` when `--config-file` is omitted.
+const DEFAULT_CONFIG_FILE: &str = "zenn2press.json";
+
+/// `Task` is the work requested on the command line, with its properties.
+#[derive(Debug)]
+pub enum Task {
+    /// Convert the Zenn repository.
+    Convert(Properties),
+    /// Convert the Zenn repository, then convert the files that change.
+    Watch(Properties),
+    /// Check the Zenn repository without writing anything.
+    Check(Properties),
+    /// List the content of the Zenn repository.
+    List(ListProperties),
+    /// Write a starter configuration file.
+    Init(InitProperties),
+}
+
 /// `Properties` is a structure that holds the configuration and paths
 /// derived from the command-line arguments.
 #[derive(Debug)]
 pub struct Properties {
-    pub src_articles_dir: String,
-    pub dest_articles_dir: String,
-    pub src_images_dir: String,
-    pub dest_images_dir: String,
-    pub include: Option<Vec<String>>,
-    pub exclude: Option<Vec<String>>,
+    pub src_dir: String,
+    pub dest_dir: Option<String>,
+    pub dest_images_dir: Option<String>,
+    pub dest_books_dir: Option<String>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub include_books: Vec<String>,
+    pub exclude_books: Vec<String>,
     pub config_file: Option<String>,
+    pub keep_going: bool,
+    pub prune: bool,
+    pub dry_run: bool,
+    pub diff: bool,
+    pub report: Option<ReportFormat>,
+    pub report_file: Option<String>,
 }
 
-/// This function parses the command-line arguments and returns a `Properties` struct
-/// containing the derived paths and configurations.
+/// `ListProperties` holds the properties of the `list` subcommand.
+#[derive(Debug)]
+pub struct ListProperties {
+    pub src_dir: String,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub include_books: Vec<String>,
+    pub exclude_books: Vec<String>,
+    pub config_file: Option<String>,
+    pub format: ReportFormat,
+}
+
+/// `InitProperties` holds the properties of the `init` subcommand.
+#[derive(Debug)]
+pub struct InitProperties {
+    pub config_file: String,
+    pub paths: PathsConfig,
+    pub filters: FiltersConfig,
+    pub force: bool,
+}
+
+/// This function parses the command-line arguments and returns the requested `Task`
+/// with the properties derived from the arguments and the configuration file.
 ///
 /// # Returns
 ///
-/// * `Properties` - A structure holding all the relevant paths and options derived from the arguments.
-pub fn get_properties() -> Properties {
-    // Parse the command-line arguments into an `Args` struct
+/// * `Task` - The subcommand to run, holding all the relevant paths and options.
+pub fn get_task() -> Task {
+    // Parse the command-line arguments, converting when no subcommand is given
     let args = Args::parse();
+    let command = args.command.unwrap_or(Command::Convert(args.convert));
 
-    // Print configuration details for debugging
-    print_configuration_details(&args);
+    match command {
+        Command::Convert(args) => Task::Convert(Properties {
+            keep_going: args.keep_going,
+            prune: args.prune,
+            dry_run: args.dry_run,
+            diff: args.diff,
+            ..properties(args.common, args.report, true)
+        }),
+        Command::Watch(args) => Task::Watch(Properties {
+            keep_going: args.keep_going,
+            prune: args.prune,
+            ..properties(args.common, ReportArgs::default(), true)
+        }),
+        // Checking converts every file in dry-run mode, to report all the problems at once.
+        // Without destination directories, the outputs are checked against empty ones.
+        Command::Check(args) => Task::Check(Properties {
+            keep_going: true,
+            dry_run: true,
+            diff: args.diff,
+            ..properties(args.common, args.report, false)
+        }),
+        Command::List(args) => {
+            let mut common = complete(args.common);
+            Task::List(ListProperties {
+                src_dir: required_arg(common.src_dir.take(), "--src-dir", "src_dir"),
+                include: common.include,
+                exclude: common.exclude,
+                include_books: common.include_book,
+                exclude_books: common.exclude_book,
+                config_file: common.config_file,
+                format: args.format,
+            })
+        }
+        // The configuration file is written, not read. The given directories are
+        // written relative to it, and the defaults are relative to it already.
+        Command::Init(args) => {
+            let common = args.common;
+            let config_file = common
+                .config_file
+                .unwrap_or_else(|| DEFAULT_CONFIG_FILE.to_string());
+            let relative = |dir: String| relative_to_config(dir, &config_file);
+            Task::Init(InitProperties {
+                paths: PathsConfig {
+                    src_dir: Some(
+                        common
+                            .src_dir
+                            .map(relative)
+                            .unwrap_or_else(|| ".".to_string()),
+                    ),
+                    dest_dir: Some(
+                        common
+                            .dest_dir
+                            .map(relative)
+                            .unwrap_or_else(|| "docs/articles".to_string()),
+                    ),
+                    dest_images_dir: Some(
+                        common
+                            .dest_images_dir
+                            .map(relative)
+                            .unwrap_or_else(|| "docs/public/images".to_string()),
+                    ),
+                    dest_books_dir: common.dest_books_dir.map(relative),
+                    ..Default::default()
+                },
+                filters: FiltersConfig {
+                    include: common.include,
+                    exclude: common.exclude,
+                    books: BookFiltersConfig {
+                        include: common.include_book,
+                        exclude: common.exclude_book,
+                    },
+                    ..Default::default()
+                },
+                force: args.force,
+                config_file,
+            })
+        }
+    }
+}
 
-    // Return the populated `Properties` struct
+/// Derives the properties of a conversion from the common and report arguments.
+/// The source directory is required, the articles and images destination directories
+/// if `require_dest` is set, and the options of the subcommand are left unset.
+fn properties(common: CommonArgs, report: ReportArgs, require_dest: bool) -> Properties {
+    let mut common = complete(common);
+    if require_dest {
+        let dest_dir = required_arg(common.dest_dir.take(), "--dest-dir", "dest_dir");
+        let dest_images_dir = required_arg(
+            common.dest_images_dir.take(),
+            "--dest-images-dir",
+            "dest_images_dir",
+        );
+        common.dest_dir = Some(dest_dir);
+        common.dest_images_dir = Some(dest_images_dir);
+    }
     Properties {
-        config_file: args.config_file,
-        src_articles_dir: format!("{}{}", args.src_dir, ARTICLES_DIR),
-        dest_articles_dir: args.dest_dir.clone(),
-        src_images_dir: format!("{}{}", args.src_dir, IMAGES_DIR),
-        dest_images_dir: args.dest_images_dir,
-        include: Some(args.include),
-        exclude: Some(args.exclude),
+        src_dir: required_arg(common.src_dir.take(), "--src-dir", "src_dir"),
+        dest_dir: common.dest_dir,
+        dest_images_dir: common.dest_images_dir,
+        dest_books_dir: common.dest_books_dir,
+        include: common.include,
+        exclude: common.exclude,
+        include_books: common.include_book,
+        exclude_books: common.exclude_book,
+        config_file: common.config_file,
+        keep_going: false,
+        prune: false,
+        dry_run: false,
+        diff: false,
+        report: report
+            .report
+            .or(report.report_file.is_some().then_some(ReportFormat::Json)),
+        report_file: report.report_file,
+    }
+}
+
+/// Reads the configuration file, if provided, to fill in the omitted common arguments.
+fn complete(mut common: CommonArgs) -> CommonArgs {
+    let config = match common.config_file.as_deref().map(read_config_file) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => Args::command().error(ErrorKind::InvalidValue, e).exit(),
+        None => ConfigFile::default(),
+    };
+    common.src_dir = common.src_dir.or(config.paths.src_dir);
+    common.dest_dir = common.dest_dir.or(config.paths.dest_dir);
+    common.dest_images_dir = common.dest_images_dir.or(config.paths.dest_images_dir);
+    common.dest_books_dir = common.dest_books_dir.or(config.paths.dest_books_dir);
+    if common.include.is_empty() {
+        common.include = config.filters.include;
+    }
+    if common.exclude.is_empty() {
+        common.exclude = config.filters.exclude;
+    }
+    if common.include_book.is_empty() {
+        common.include_book = config.filters.books.include;
     }
+    if common.exclude_book.is_empty() {
+        common.exclude_book = config.filters.books.exclude;
+    }
+    common
+}
+
+/// Rewrites a directory given relative to the working directory as relative to the
+/// directory of the configuration file, which is how the `paths` section is read.
+fn relative_to_config(dir: String, config_file: &str) -> String {
+    let config_dir = Path::new(config_file).parent().unwrap_or(Path::new(""));
+    if config_dir.as_os_str().is_empty() || Path::new(&dir).is_absolute() {
+        return dir;
+    }
+    let Ok(current_dir) = env::current_dir() else {
+        return dir;
+    };
+
+    // Climb from the configuration directory to the common ancestor, then down to the directory
+    let dir = normalize(&current_dir.join(&dir));
+    let config_dir = normalize(&current_dir.join(config_dir));
+    let common = dir
+        .components()
+        .zip(config_dir.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative: PathBuf = config_dir.components().skip(common).map(|_| "..").collect();
+    relative.extend(dir.components().skip(common));
+
+    if relative.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        relative.to_string_lossy().to_string()
+    }
+}
+
+/// Removes the `.` and `..` components of an absolute path, without reading the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Unwraps a required argument, already completed from the configuration file.
+/// Exits with a usage error when neither provides it.
+fn required_arg(arg: Option<String>, flag: &str, key: &str) -> String {
+    arg.unwrap_or_else(|| {
+        let message = format!(
+            "the following required argument was not provided: {} <DIR> (or `paths.{}` in the configuration file)",
+            flag, key
+        );
+        Args::command()
+            .error(ErrorKind::MissingRequiredArgument, message)
+            .exit()
+    })
 }
 
 /// Prints the configuration details, including the configuration file,
-/// articles and images directories, and included/excluded files.
-fn print_configuration_details(args: &Args) {
-    println!(
-        "{} {} {}",
-        "[1/3]".bright_black().bold(),
-        "📝",
+/// articles, images and books directories, and included/excluded files.
+pub fn print_configuration_details(properties: &Properties) {
+    outln!(
+        "{} 📝 {}",
+        "[1/4]".bright_black().bold(),
         "Configuration Details:".bold().underline()
     );
 
     // Print config file
-    print_config_file(&args.config_file);
+    print_config_file(&properties.config_file);
 
     // Print articles and images directories
-    print_directories(&args.src_dir, &args.dest_dir);
+    print_directories(
+        &properties.src_dir,
+        &properties.dest_dir,
+        &properties.dest_images_dir,
+        &properties.dest_books_dir,
+    );
 
     // Print include and exclude files
-    print_include_exclude_files(&args.include, &args.exclude);
+    print_include_exclude_files(&properties.include, &properties.exclude);
+
+    // Print include and exclude books, if any
+    print_book_filters(&properties.include_books, &properties.exclude_books);
 }
 
 /// Prints the configuration file path or "None" if not available.
 fn print_config_file(config_file: &Option<String>) {
     match config_file {
-        Some(config) => println!("- {} {}", "Configuration File:".green().bold(), config),
-        None => println!(
+        Some(config) => outln!("- {} {}", "Configuration File:".green().bold(), config),
+        None => outln!(
             "- {} {}",
             "Configuration File:".green().bold(),
             "None".yellow().bold()
@@ -76,46 +325,70 @@ fn print_config_file(config_file: &Option<String>) {
     }
 }
 
-/// Prints the source and destination directories for articles and images.
-fn print_directories(src_dir: &str, dest_dir: &str) {
-    let src_articles_dir = format!("{}{}", src_dir, ARTICLES_DIR);
-    let src_images_dir = format!("{}{}", src_dir, IMAGES_DIR);
+/// Prints the source and destination directories for articles, images and books.
+fn print_directories(
+    src_dir: &str,
+    dest_dir: &Option<String>,
+    dest_images_dir: &Option<String>,
+    dest_books_dir: &Option<String>,
+) {
+    let src_dir = Path::new(src_dir);
+    print_directory("Articles:", &src_dir.join(ARTICLES_DIR), dest_dir);
+    print_directory("Images:", &src_dir.join(IMAGES_DIR), dest_images_dir);
+    print_directory("Books:", &src_dir.join(BOOKS_DIR), dest_books_dir);
+}
 
-    println!(
-        "- {} {:?} {} {:?}",
-        "Articles:".green().bold(),
-        src_articles_dir,
-        "=>".yellow().bold(),
-        dest_dir
-    );
-    println!(
-        "- {} {:?} {} {:?}",
-        "Images:".green().bold(),
-        src_images_dir,
-        "=>".yellow().bold(),
-        dest_dir
-    );
+/// Prints a source directory and its destination, or "None" if the destination is not set.
+fn print_directory(label: &str, src_dir: &Path, dest_dir: &Option<String>) {
+    match dest_dir {
+        Some(dest_dir) => outln!(
+            "- {} {:?} {} {:?}",
+            label.green().bold(),
+            src_dir,
+            "=>".yellow().bold(),
+            dest_dir
+        ),
+        None => outln!("- {} {}", label.green().bold(), "None".yellow().bold()),
+    }
 }
 
 /// Prints the list of included and excluded files, or "None" if the lists are empty.
 fn print_include_exclude_files(include: &Vec<String>, exclude: &Vec<String>) {
     // Include Files handling
-    println!("- {}", "Include Files:".green().bold());
+    outln!("- {}", "Include Files:".green().bold());
     if include.is_empty() {
-        println!("\t- {}", "None");
+        outln!("\t- None");
     } else {
         for file in include {
-            println!("\t- {}", file);
+            outln!("\t- {}", file);
         }
     }
 
     // Exclude Files handling
-    println!("- {}", "Exclude Files:".green().bold());
+    outln!("- {}", "Exclude Files:".green().bold());
     if exclude.is_empty() {
-        println!("\t- {}", "None");
+        outln!("\t- None");
     } else {
         for file in exclude {
-            println!("\t- {}", file);
+            outln!("\t- {}", file);
         }
     }
 }
+
+/// Prints the lists of included and excluded books, if they are set.
+fn print_book_filters(include_books: &[String], exclude_books: &[String]) {
+    if !include_books.is_empty() {
+        outln!(
+            "- {} {}",
+            "Include Books:".green().bold(),
+            include_books.join(", ")
+        );
+    }
+    if !exclude_books.is_empty() {
+        outln!(
+            "- {} {}",
+            "Exclude Books:".green().bold(),
+            exclude_books.join(", ")
+        );
+    }
+}
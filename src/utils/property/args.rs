@@ -1,33 +1,178 @@
 use crate::constants::DESCRIPTION;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// `ReportFormat` is the format of the conversion report or the content listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Pretty-printed JSON, with a summary and an entry per file.
+    Json,
+    /// A summary line followed by a line per file.
+    Text,
+}
 
 /// `Args` is a structure representing the command-line arguments accepted by the application.
 /// The `clap` crate is used to parse and validate these arguments.
+///
+/// Without a subcommand, the arguments of `convert` are accepted, so `zenn2press -s <DIR> ...`
+/// keeps converting as before.
 #[derive(Parser)]
-#[command(version, about = DESCRIPTION)]
-#[command(next_line_help = true)]
+#[command(version, about = DESCRIPTION, long_about = None)]
+#[command(next_line_help = true, args_conflicts_with_subcommands = true)]
 pub struct Args {
-    /// Path of the root directory of Zenn content.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub convert: ConvertArgs,
+}
+
+/// `Command` is a subcommand of the application.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Convert the articles, books and images into the VitePress directories.
+    Convert(ConvertArgs),
+    /// Convert everything, then watch the Zenn repository and convert the files that change.
+    Watch(WatchArgs),
+    /// Validate the articles, books and images without writing anything.
+    Check(CheckArgs),
+    /// Print the articles, books and images found in the Zenn repository, with their metadata.
+    List(ListArgs),
+    /// Write a starter configuration file (`--config-file`, `zenn2press.json` by default).
+    Init(InitArgs),
+}
+
+/// `CommonArgs` holds the options shared by every subcommand: the directories,
+/// the configuration file and the filters.
+#[derive(clap::Args)]
+#[command(next_help_heading = "Common options")]
+pub struct CommonArgs {
+    /// Path of the root directory of Zenn content. Defaults to `paths.src_dir` of the configuration file.
     #[arg(short, long, value_name = "DIR")]
-    pub src_dir: String,
+    pub src_dir: Option<String>,
 
     /// The VitePress directory path (e.g. docs/entries) where you want to place the markdown for the articles.
+    /// Defaults to `paths.dest_dir` of the configuration file.
     #[arg(short = 'd', long, value_name = "DIR")]
-    pub dest_dir: String,
+    pub dest_dir: Option<String>,
 
     /// The VitePress directory path (e.g. public) where the image will be placed.
+    /// Defaults to `paths.dest_images_dir` of the configuration file.
     #[arg(short = 'm', long, value_name = "DIR")]
-    pub dest_images_dir: String,
+    pub dest_images_dir: Option<String>,
+
+    /// The VitePress directory path (e.g. docs/books) where the books will be placed.
+    /// Defaults to `paths.dest_books_dir` of the configuration file.
+    #[arg(short = 'b', long, value_name = "DIR")]
+    pub dest_books_dir: Option<String>,
 
     /// Configuration File Path.
     #[arg(short, long, value_name = "FILE")]
     pub config_file: Option<String>,
 
-    /// File names to include.
+    /// File names to include. Defaults to `filters.include` of the configuration file.
     #[arg(short, long, value_name = "<FILE>", value_delimiter = ',')]
     pub include: Vec<String>,
 
-    /// File names to exclude.
+    /// File names to exclude. Defaults to `filters.exclude` of the configuration file.
     #[arg(short, long, value_name = "<FILE>", value_delimiter = ',')]
     pub exclude: Vec<String>,
+
+    /// Book slugs to include. Defaults to `filters.books.include` of the configuration file.
+    #[arg(long, value_name = "<SLUG>", value_delimiter = ',')]
+    pub include_book: Vec<String>,
+
+    /// Book slugs to exclude. Defaults to `filters.books.exclude` of the configuration file.
+    #[arg(long, value_name = "<SLUG>", value_delimiter = ',')]
+    pub exclude_book: Vec<String>,
+}
+
+/// `ReportArgs` holds the options for printing or writing the conversion report.
+#[derive(clap::Args, Default)]
+pub struct ReportArgs {
+    /// Print a report of every converted, unchanged, skipped, failed and deleted file in the given format.
+    #[arg(short, long, value_name = "FORMAT")]
+    pub report: Option<ReportFormat>,
+
+    /// Write the report to a file instead of the standard output. The format defaults to JSON.
+    #[arg(long, value_name = "FILE")]
+    pub report_file: Option<String>,
+}
+
+/// `ConvertArgs` holds the arguments of the `convert` subcommand.
+#[derive(clap::Args)]
+pub struct ConvertArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// Keep converting the other files when one fails, and list every failed file at the end.
+    #[arg(short, long)]
+    pub keep_going: bool,
+
+    /// Delete the articles and images written by a previous run whose Zenn source was removed.
+    /// Only files listed in the manifest of each destination directory are deleted.
+    #[arg(long)]
+    pub prune: bool,
+
+    /// Convert the files without writing or deleting anything, and print the planned changes.
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+
+    /// Print a unified diff of each Markdown output against the file on disk.
+    #[arg(long)]
+    pub diff: bool,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+/// `WatchArgs` holds the arguments of the `watch` subcommand.
+#[derive(clap::Args)]
+pub struct WatchArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// Keep converting the other files when one fails, and list every failed file at the end.
+    #[arg(short, long)]
+    pub keep_going: bool,
+
+    /// Delete the articles and images written by a previous run whose Zenn source was removed.
+    /// Only files listed in the manifest of each destination directory are deleted.
+    #[arg(long)]
+    pub prune: bool,
+}
+
+/// `CheckArgs` holds the arguments of the `check` subcommand.
+#[derive(clap::Args)]
+pub struct CheckArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// Print a unified diff of each Markdown output against the file on disk.
+    #[arg(long)]
+    pub diff: bool,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+/// `ListArgs` holds the arguments of the `list` subcommand.
+#[derive(clap::Args)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// The format of the listing.
+    #[arg(short, long, value_name = "FORMAT", default_value = "text")]
+    pub format: ReportFormat,
+}
+
+/// `InitArgs` holds the arguments of the `init` subcommand.
+#[derive(clap::Args)]
+pub struct InitArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+
+    /// Replace the configuration file if it already exists.
+    #[arg(long)]
+    pub force: bool,
 }
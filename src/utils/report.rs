@@ -0,0 +1,185 @@
+use std::fs;
+
+use colored::Colorize;
+use zenn2press::report::{ConversionReport, FileStatus};
+
+use super::property::ReportFormat;
+
+/// Prints every failed file of the report with its error to the standard error.
+///
+/// # Arguments
+///
+/// * `report` - The report of the conversion.
+pub fn print_failures(report: &ConversionReport) {
+    let failures: Vec<_> = report.with_status(FileStatus::Failed).collect();
+    if failures.is_empty() {
+        return;
+    }
+
+    eprintln!("Failed {} file(s):", failures.len());
+    for file in failures {
+        let message = file.message.as_deref().unwrap_or_default();
+        eprintln!(
+            "\t- {:?} ({})",
+            file.source.file_name().unwrap_or_default(),
+            message.replace('\n', "\n\t")
+        );
+    }
+}
+
+/// Prints the warnings raised by the conversion of every file.
+///
+/// # Arguments
+///
+/// * `report` - The report of the conversion.
+pub fn print_warnings(report: &ConversionReport) {
+    for file in &report.files {
+        for warning in &file.warnings {
+            outln!(
+                "{} {} in {:?}",
+                "warning:".yellow().bold(),
+                warning,
+                file.source
+            );
+        }
+    }
+}
+
+/// Prints every skipped file with the reason it was left out.
+///
+/// # Arguments
+///
+/// * `report` - The report of the conversion.
+pub fn print_skipped(report: &ConversionReport) {
+    let skipped: Vec<_> = report.with_status(FileStatus::Skipped).collect();
+    if skipped.is_empty() {
+        return;
+    }
+
+    outln!("Skipped {} file(s):", skipped.len());
+    for file in skipped {
+        outln!(
+            "\t- {:?} ({})",
+            file.source.file_name().unwrap_or_default(),
+            file.message.as_deref().unwrap_or_default()
+        );
+    }
+}
+
+/// Prints every output deleted by `--prune`.
+///
+/// # Arguments
+///
+/// * `report` - The report of the conversion.
+pub fn print_deletions(report: &ConversionReport) {
+    let deletions: Vec<_> = report.with_status(FileStatus::Deleted).collect();
+    if deletions.is_empty() {
+        return;
+    }
+
+    outln!("Deleted {} file(s):", deletions.len());
+    for file in deletions {
+        outln!(
+            "\t- {:?}",
+            file.destination.as_deref().unwrap_or(&file.source)
+        );
+    }
+}
+
+/// Prints the change planned for every file in dry-run mode.
+///
+/// # Arguments
+///
+/// * `report` - The report of the dry run.
+pub fn print_plan(report: &ConversionReport) {
+    outln!("Planned changes (nothing was written):");
+    for file in &report.files {
+        let Some(action) = file.action else {
+            continue;
+        };
+        outln!(
+            "\t{:<9} {:<7} {:?}",
+            action,
+            file.kind,
+            file.destination.as_deref().unwrap_or(&file.source)
+        );
+    }
+}
+
+/// Prints the outcome of `check`: the number of files checked, failed and warned about.
+///
+/// # Arguments
+///
+/// * `report` - The report of the dry run.
+pub fn print_check(report: &ConversionReport) {
+    let summary = report.summary();
+    let warnings: usize = report.files.iter().map(|file| file.warnings.len()).sum();
+    let message = format!(
+        "Checked {} file(s): {} failed, {} skipped, {} warning(s)",
+        report.files.len(),
+        summary.failed,
+        summary.skipped,
+        warnings
+    );
+    if summary.failed == 0 {
+        outln!("✅ {}", message.green().bold());
+    } else {
+        outln!("❌ {}", message.red().bold());
+    }
+}
+
+/// Prints the unified diff of every Markdown output, coloring the added and removed lines.
+///
+/// # Arguments
+///
+/// * `report` - The report of the conversion.
+pub fn print_diffs(report: &ConversionReport) {
+    for diff in report.files.iter().filter_map(|file| file.diff.as_deref()) {
+        for line in diff.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                outln!("{}", line.bold());
+            } else if line.starts_with('+') {
+                outln!("{}", line.green());
+            } else if line.starts_with('-') {
+                outln!("{}", line.red());
+            } else if line.starts_with("@@") {
+                outln!("{}", line.cyan());
+            } else {
+                outln!("{}", line);
+            }
+        }
+    }
+}
+
+/// Renders the report in the given format and writes it to a file, or prints it if no file is given.
+///
+/// # Arguments
+///
+/// * `report` - The report of the conversion.
+/// * `format` - The format of the report.
+/// * `report_file` - The optional path of the file to write the report to.
+///
+/// # Returns
+///
+/// * `zenn2press::Result<()>` - An error if the report cannot be serialized or written.
+pub fn write_report(
+    report: &ConversionReport,
+    format: ReportFormat,
+    report_file: Option<&str>,
+) -> zenn2press::Result<()> {
+    let output = match format {
+        ReportFormat::Json => report.to_json()?,
+        ReportFormat::Text => report.to_string(),
+    };
+
+    match report_file {
+        Some(path) => fs::write(path, format!("{}\n", output)).map_err(|e| zenn2press::Error::Io {
+            path: path.into(),
+            source: e,
+        }),
+        None => {
+            println!("{}", output);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use colored::Colorize;
+use zenn2press::{
+    watch::{Changes, Watch, DEFAULT_DEBOUNCE},
+    ConverterBuilder,
+};
+
+use super::report::{print_deletions, print_failures};
+
+/// Watches the Zenn repository and converts the files that change, until the process is stopped.
+///
+/// # Arguments
+///
+/// * `builder` - The builder of the converter, built again when the configuration file changes.
+pub async fn watch(builder: ConverterBuilder) {
+    let mut watch = Watch::new(builder, DEFAULT_DEBOUNCE).unwrap_or_else(|e| {
+        eprintln!("Error watching the Zenn repository: {}", e);
+        std::process::exit(1);
+    });
+
+    outln!(
+        "\n👀 {}",
+        "Watching for changes (press Ctrl+C to stop)...".bold()
+    );
+
+    loop {
+        // Wait for the next changes without blocking the other tasks of the runtime
+        let changes = match tokio::task::block_in_place(|| watch.next_changes(None)) {
+            Ok(Some(changes)) => changes,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("Error watching the Zenn repository: {}", e);
+                continue;
+            }
+        };
+        print_changes(&changes);
+
+        // Convert the changes, and keep watching if they fail
+        match watch.apply(&changes).await {
+            Ok(report) => {
+                outln!("{}", report);
+                print_deletions(&report);
+                print_failures(&report);
+            }
+            Err(e) => eprintln!("Error converting the changes: {}", e),
+        }
+    }
+}
+
+/// Prints the changed files and the conversions they affect.
+fn print_changes(changes: &Changes) {
+    outln!(
+        "\n{} {}",
+        "🔄".bold(),
+        "Changes detected:".bold().underline()
+    );
+    if changes.config {
+        outln!(
+            "- {} converting everything",
+            "Configuration:".green().bold()
+        );
+        return;
+    }
+    for article in &changes.articles {
+        outln!("- {} {}", "Article:".green().bold(), article);
+    }
+    for book in &changes.books {
+        outln!("- {} {}", "Book:".green().bold(), book);
+    }
+    for image in &changes.images {
+        outln!("- {} {}", "Image:".green().bold(), image.display());
+    }
+}
@@ -0,0 +1,451 @@
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+    DebounceEventResult, Debouncer, RecommendedCache,
+};
+
+use crate::converter::{Converter, ConverterBuilder};
+use crate::error::{Error, IoResultExt, Result};
+use crate::image::copy::copy_files;
+use crate::manifest::prune_outputs;
+use crate::markdown::filter::filter_markdown_files;
+use crate::report::{ConversionReport, FileKind};
+
+/// The time to wait for more events before converting the changes.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// `Changes` groups the changed files of the Zenn repository by the conversion they affect.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes {
+    /// Whether the configuration file changed, in which case everything is converted again.
+    pub config: bool,
+    /// The file names of the changed articles.
+    pub articles: BTreeSet<String>,
+    /// The paths of the changed images, relative to the images directory.
+    pub images: BTreeSet<PathBuf>,
+    /// The slugs of the changed books.
+    pub books: BTreeSet<String>,
+}
+
+impl Changes {
+    /// Returns whether no conversion is affected.
+    pub fn is_empty(&self) -> bool {
+        !self.config && self.articles.is_empty() && self.images.is_empty() && self.books.is_empty()
+    }
+}
+
+/// `WatchedPaths` holds the absolute paths that are watched, to classify the changed files.
+#[derive(Debug, Clone, Default)]
+struct WatchedPaths {
+    articles: Option<PathBuf>,
+    images: Option<PathBuf>,
+    books: Option<PathBuf>,
+    config: Option<PathBuf>,
+}
+
+impl WatchedPaths {
+    /// Adds a changed file to the conversions it affects.
+    fn classify(&self, path: &Path, changes: &mut Changes) {
+        if self.config.as_deref() == Some(path) {
+            changes.config = true;
+        } else if let Some(name) = relative(path, &self.articles).and_then(single_name) {
+            if name.ends_with(".md") {
+                changes.articles.insert(name);
+            }
+        } else if let Some(relative) = relative(path, &self.images) {
+            // Directories are copied through the files they contain
+            if !path.is_dir() {
+                changes.images.insert(relative.to_path_buf());
+            }
+        } else if let Some(relative) = relative(path, &self.books) {
+            if let Some(Component::Normal(slug)) = relative.components().next() {
+                changes.books.insert(slug.to_string_lossy().to_string());
+            }
+        }
+    }
+}
+
+/// Returns the path of a file relative to a watched directory, if it is inside it.
+fn relative<'a>(path: &'a Path, dir: &Option<PathBuf>) -> Option<&'a Path> {
+    let relative = path.strip_prefix(dir.as_deref()?).ok()?;
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+/// Returns the name of a file directly inside a watched directory.
+fn single_name(relative: &Path) -> Option<String> {
+    let mut components = relative.components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) => Some(name.to_string_lossy().to_string()),
+        _ => None,
+    }
+}
+
+/// `Watch` watches a Zenn repository and converts the files that change.
+///
+/// The articles, images and books directories are watched recursively, along with the
+/// configuration file. Events are debounced, then grouped into [`Changes`] that are
+/// converted with [`Watch::apply`]. When the configuration file changes, the converter
+/// is built again and everything is converted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use zenn2press::{watch::Watch, Converter};
+///
+/// #[tokio::main]
+/// async fn main() -> zenn2press::Result<()> {
+///     let builder = Converter::builder()
+///         .src_dir("demo/zenn")
+///         .dest_dir("demo/press/docs/articles")
+///         .dest_images_dir("demo/press/docs/public/images");
+///
+///     let mut watch = Watch::new(builder, zenn2press::watch::DEFAULT_DEBOUNCE)?;
+///     while let Some(changes) = watch.next_changes(None)? {
+///         println!("{}", watch.apply(&changes).await?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Watch {
+    builder: ConverterBuilder,
+    converter: Converter,
+    paths: WatchedPaths,
+    events: Receiver<DebounceEventResult>,
+    // Dropping the debouncer stops watching
+    _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+}
+
+impl Watch {
+    /// Builds the converter and starts watching its source directories and configuration file.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The builder of the converter, built again when the configuration file changes.
+    /// * `debounce` - The time to wait for more events before reporting the changes.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Watch>` - The watch, or an error if the converter cannot be built or a
+    ///   directory cannot be watched.
+    pub fn new(builder: ConverterBuilder, debounce: Duration) -> Result<Watch> {
+        let converter = builder.clone().build()?;
+
+        let (sender, events) = channel();
+        let mut debouncer = new_debouncer(debounce, None, sender)
+            .map_err(|e| Error::io("", io::Error::other(e)))?;
+
+        // Watch the source directories that exist, by their absolute path
+        let mut paths = WatchedPaths::default();
+        let dirs = [
+            (&converter.src_articles_dir, &mut paths.articles),
+            (&converter.src_images_dir, &mut paths.images),
+            (&converter.src_books_dir, &mut paths.books),
+        ];
+        for (dir, watched) in dirs {
+            let Some(dir) = dir.as_deref().filter(|dir| Path::new(dir).is_dir()) else {
+                continue;
+            };
+            let dir = fs::canonicalize(dir).with_path(dir)?;
+            debouncer
+                .watch(&dir, RecursiveMode::Recursive)
+                .map_err(|e| Error::io(&dir, io::Error::other(e)))?;
+            *watched = Some(dir);
+        }
+
+        // Watch the directory of the configuration file, as editors often replace the file
+        if let Some(config_file) = &converter.config_file {
+            let config_file = fs::canonicalize(config_file).with_path(config_file)?;
+            let dir = config_file.parent().unwrap_or(Path::new("/"));
+            debouncer
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(|e| Error::io(dir, io::Error::other(e)))?;
+            paths.config = Some(config_file);
+        }
+
+        Ok(Watch {
+            builder,
+            converter,
+            paths,
+            events,
+            _debouncer: debouncer,
+        })
+    }
+
+    /// Returns the current converter.
+    pub fn converter(&self) -> &Converter {
+        &self.converter
+    }
+
+    /// Waits for the next changes in the watched files.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The longest time to wait, or `None` to wait until a change.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<Changes>>` - The changes, `None` if the timeout elapsed or the
+    ///   watcher stopped, or an error reported by the watcher.
+    pub fn next_changes(&self, timeout: Option<Duration>) -> Result<Option<Changes>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let events = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    match self.events.recv_timeout(remaining) {
+                        Ok(events) => events,
+                        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => {
+                            return Ok(None)
+                        }
+                    }
+                }
+                None => match self.events.recv() {
+                    Ok(events) => events,
+                    Err(_) => return Ok(None),
+                },
+            };
+            let events = events.map_err(|mut errors| {
+                let e = errors.remove(0);
+                let path = e.paths.first().cloned().unwrap_or_default();
+                Error::io(path, io::Error::other(e))
+            })?;
+
+            // Ignore reads, such as those of the conversion itself, and files that do not
+            // affect a conversion, e.g. in the config file's directory
+            let mut changes = Changes::default();
+            for event in events.iter().filter(|event| !event.kind.is_access()) {
+                for path in &event.paths {
+                    self.paths.classify(path, &mut changes);
+                }
+            }
+            if !changes.is_empty() {
+                return Ok(Some(changes));
+            }
+        }
+    }
+
+    /// Converts the changes, building the converter again if the configuration file changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `changes` - The changes returned by [`Watch::next_changes`].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConversionReport>` - The outcome of every converted file, or the error of
+    ///   the configuration or the first conversion that stops. After a configuration error,
+    ///   the previous converter is kept.
+    pub async fn apply(&mut self, changes: &Changes) -> Result<ConversionReport> {
+        if changes.config {
+            self.converter = self.builder.clone().build()?;
+        }
+        self.converter.convert_changes(changes).await
+    }
+}
+
+/// Converts the files affected by changes in the Zenn repository.
+///
+/// A change to the configuration converts everything. Otherwise, only the changed
+/// articles, images and books are converted, among those selected by the include and
+/// exclude lists of their kind. The articles are all processed when a sidebar is configured, so that
+/// it lists every article; the manifest skips those that are unchanged. With `prune`,
+/// the outputs of deleted articles and images are deleted.
+///
+/// # Arguments
+///
+/// * `converter` - The converter.
+/// * `changes` - The changes to convert.
+///
+/// # Returns
+///
+/// * `Result<ConversionReport>` - The outcome of every converted file.
+pub(crate) async fn convert_changes(
+    converter: &Converter,
+    changes: &Changes,
+) -> Result<ConversionReport> {
+    if changes.config {
+        return converter.run().await;
+    }
+    let mut report = ConversionReport::default();
+
+    // Convert the changed articles, or all of them to keep the sidebar complete
+    if !changes.articles.is_empty() {
+        let has_sidebar = converter
+            .config()
+            .is_some_and(|config| config.output.sidebar.is_some());
+        if has_sidebar {
+            report.extend(converter.convert_articles()?);
+        } else {
+            let files = changes.articles.iter().map(PathBuf::from).collect();
+            let names: Vec<String> =
+                filter_markdown_files(files, converter.include(), converter.exclude())
+                    .iter()
+                    .filter_map(|file| Some(file.file_stem()?.to_string_lossy().to_string()))
+                    .collect();
+            if !names.is_empty() {
+                report.extend(narrowed(converter, names).convert_articles()?);
+            }
+        }
+    }
+
+    // Convert the changed books
+    if !changes.books.is_empty() && converter.dest_books_dir.is_some() {
+        let slugs: Vec<String> = changes
+            .books
+            .iter()
+            .filter(|slug| converter.is_book_selected(slug))
+            .cloned()
+            .collect();
+        if !slugs.is_empty() {
+            let converter = Converter {
+                include_books: slugs,
+                ..converter.clone()
+            };
+            report.extend(converter.convert_books()?);
+        }
+    }
+
+    // Copy the changed images, and delete the outputs of the removed ones
+    let images_dirs = converter
+        .images_dirs()
+        .filter(|_| !changes.images.is_empty());
+    if let Some((src_dir, dest_dir)) = images_dirs {
+        let file_paths: Vec<PathBuf> = changes
+            .images
+            .iter()
+            .map(|relative| Path::new(src_dir).join(relative))
+            .filter(|file| file.is_file() && is_selected(converter, file))
+            .collect();
+        let mode = converter.output_mode();
+        report.extend(copy_files(
+            &file_paths,
+            src_dir,
+            dest_dir,
+            converter.keep_going,
+            mode,
+        )?);
+        let removed = changes
+            .images
+            .iter()
+            .any(|relative| !Path::new(src_dir).join(relative).exists());
+        if converter.prune && removed {
+            for pruned in prune_outputs(src_dir, dest_dir, FileKind::Image, mode)? {
+                report.push(pruned);
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Returns a copy of the converter that only converts the files with the given names.
+fn narrowed(converter: &Converter, names: Vec<String>) -> Converter {
+    Converter {
+        include: names,
+        ..converter.clone()
+    }
+}
+
+/// Returns whether an image is selected by the include and exclude lists, as in `get_file_paths`.
+fn is_selected(converter: &Converter, file: &Path) -> bool {
+    let path = file.to_string_lossy();
+    let included =
+        converter.include.is_empty() || converter.include.iter().any(|inc| path.contains(inc));
+    let excluded = converter.exclude.iter().any(|exc| path.contains(exc));
+    included && !excluded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::{
+        tests::{write_article, zenn_repository},
+        IMAGES_DIR,
+    };
+    use crate::report::FileStatus;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_classify_changes() {
+        let paths = WatchedPaths {
+            articles: Some(PathBuf::from("/zenn/articles")),
+            images: Some(PathBuf::from("/zenn/images")),
+            books: Some(PathBuf::from("/zenn/books")),
+            config: Some(PathBuf::from("/zenn/zenn2press.json")),
+        };
+
+        let mut changes = Changes::default();
+        for path in [
+            "/zenn/articles/a.md",
+            "/zenn/articles",
+            "/zenn/images/a/b.png",
+            "/zenn/books/sample-book/1.intro.md",
+            "/zenn/README.md",
+        ] {
+            paths.classify(Path::new(path), &mut changes);
+        }
+
+        assert!(!changes.config);
+        assert_eq!(changes.articles, BTreeSet::from(["a.md".to_string()]));
+        assert_eq!(changes.images, BTreeSet::from([PathBuf::from("a/b.png")]));
+        assert_eq!(changes.books, BTreeSet::from(["sample-book".to_string()]));
+
+        paths.classify(Path::new("/zenn/zenn2press.json"), &mut changes);
+        assert!(changes.config);
+    }
+
+    #[tokio::test]
+    async fn test_watch_converts_changed_article() {
+        let (src_dir, articles_dir) = zenn_repository();
+        let dest_dir = tempdir().unwrap();
+        let images_dir = src_dir.path().join(IMAGES_DIR);
+        fs::create_dir_all(&images_dir).unwrap();
+        write_article(&articles_dir, "a.md", "Body\n");
+        write_article(&articles_dir, "b.md", "Body\n");
+        fs::write(images_dir.join("a.png"), "png").unwrap();
+
+        let builder = Converter::builder()
+            .src_dir(src_dir.path().to_string_lossy())
+            .dest_dir(dest_dir.path().to_string_lossy())
+            .dest_images_dir(dest_dir.path().join("images").to_string_lossy());
+        let mut watch = Watch::new(builder, Duration::from_millis(100)).unwrap();
+        assert_eq!(
+            watch
+                .next_changes(Some(Duration::from_millis(200)))
+                .unwrap(),
+            None
+        );
+
+        // Only the edited article is converted
+        write_article(&articles_dir, "a.md", "Edited\n");
+        let changes = loop {
+            let changes = watch
+                .next_changes(Some(Duration::from_secs(10)))
+                .unwrap()
+                .expect("no change was reported");
+            if !changes.articles.is_empty() {
+                break changes;
+            }
+        };
+        assert_eq!(changes.articles, BTreeSet::from(["a.md".to_string()]));
+        assert!(changes.images.is_empty());
+
+        let report = watch.apply(&changes).await.unwrap();
+        let converted: Vec<_> = report.with_status(FileStatus::Converted).collect();
+        assert_eq!(converted.len(), 1);
+        assert_eq!(converted[0].destination, Some(dest_dir.path().join("a.md")));
+        assert!(fs::read_to_string(dest_dir.path().join("a.md"))
+            .unwrap()
+            .ends_with("Edited\n"));
+        assert!(!dest_dir.path().join("b.md").exists());
+        assert!(!dest_dir.path().join("images").exists());
+    }
+}